@@ -22,7 +22,7 @@
 use proc_macro2::TokenStream as TokenStream2;
 use syn::Result;
 
-use crate::params::{CommitDerive, StrategyAttr};
+use crate::params::{CommitDerive, SortedKind, StrategyAttr};
 
 impl CommitDerive {
     pub fn derive_encode(&self) -> Result<TokenStream2> {
@@ -32,6 +32,61 @@ impl CommitDerive {
         let ident_name = &self.data.name;
 
         let inner = match self.conf.strategy {
+            _ if !self.variants.is_empty() => {
+                let arms = self.variants.iter().map(|variant| {
+                    let name = &variant.name;
+                    let body = match variant.strategy {
+                        _ if variant.unit => quote! {},
+                        StrategyAttr::Strict => quote! { engine.commit_to_serialized(inner); },
+                        StrategyAttr::ConcealStrict => {
+                            quote! { engine.commit_to_concealed(inner); }
+                        }
+                        StrategyAttr::Merklize => quote! { engine.commit_to_merkle(inner); },
+                        StrategyAttr::Transparent => unreachable!("checked during parsing"),
+                    };
+                    let pat = if variant.unit {
+                        quote! { Self::#name }
+                    } else {
+                        quote! { Self::#name(inner) }
+                    };
+                    quote! {
+                        #pat => {
+                            engine.commit_to_variant::<Self>(tag);
+                            #body
+                        }
+                    }
+                });
+                quote! {
+                    let tag = strict_encoding::StrictSum::variant_ord(self);
+                    match self {
+                        #( #arms )*
+                    }
+                }
+            }
+            StrategyAttr::Strict if !self.signatures.is_empty() || !self.sorted.is_empty() => {
+                let serialized = if self.signatures.is_empty() {
+                    quote! { engine.commit_to_serialized(self); }
+                } else {
+                    let members = &self.serialized;
+                    quote! {
+                        engine.commit_to_fields::<Self>();
+                        #( engine.commit_to_field(&self.#members); )*
+                    }
+                };
+                let sorted = self.sorted.iter().map(|field| {
+                    let member = &field.member;
+                    match field.kind {
+                        SortedKind::HashSet => quote! { engine.commit_to_hash_set(&self.#member); },
+                        SortedKind::HashMap => quote! { engine.commit_to_hash_map(&self.#member); },
+                    }
+                });
+                let types = self.signatures.iter().map(|sig| &sig.ty);
+                quote! {
+                    #serialized
+                    #( #sorted )*
+                    #( engine.commit_to_detached_signature::<#types>(); )*
+                }
+            }
             StrategyAttr::Strict => quote! {
                 engine.commit_to_serialized(self);
             },
@@ -48,12 +103,36 @@ impl CommitDerive {
             },
         };
 
+        let domain = if self.conf.domain {
+            quote! { engine.commit_to_domain::<Self>(); }
+        } else if self.conf.version.is_some() {
+            quote! { engine.commit_to_version::<Self>(); }
+        } else if self.conf.schema_bound {
+            quote! { engine.commit_to_lib_schema::<Self>(); }
+        } else {
+            quote! {}
+        };
+
+        let version = match self.conf.version {
+            Some(version) => quote! { const COMMITMENT_VERSION: u8 = #version; },
+            None => quote! {},
+        };
+
+        let field_count = if self.conf.field_count {
+            quote! { engine.set_field_count_guard(); }
+        } else {
+            quote! {}
+        };
+
         Ok(quote! {
             #[automatically_derived]
             impl #impl_generics #trait_crate::CommitEncode for #ident_name #ty_generics #where_clause {
                 type CommitmentId = #commitment_id;
+                #version
 
                 fn commit_encode(&self, engine: &mut #trait_crate::CommitEngine) {
+                    #field_count
+                    #domain
                     #inner
                 }
             }
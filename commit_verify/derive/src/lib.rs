@@ -44,6 +44,177 @@
 //!
 //! Derivation macros accept `#[commit_encoding()]` attribute with the following
 //! arguments:
+//!
+//! ### `crate = ::path::to::commit_verify_crate`
+//!
+//! Allows to specify custom path to `commit_verify` crate.
+//!
+//! ### `id = CommitmentIdType`
+//!
+//! Required argument specifying the type of the produced commitment id.
+//!
+//! ### `strategy = transparent|strict|conceal|merklize`
+//!
+//! Required argument specifying how the data are committed to.
+//!
+//! ### `domain`
+//!
+//! Commits to the fully qualified strict type name of the type before
+//! committing to its data, such that different types never produce the same
+//! commitment (see `CommitEngine::commit_to_domain`).
+//!
+//! ### `version = N`
+//!
+//! Sets `CommitEncode::COMMITMENT_VERSION` to `N` and commits to the fully
+//! qualified strict type name of the type together with the version before
+//! committing to its data, such that different versions of the same type never
+//! produce the same commitment (see `CommitEngine::commit_to_version`). Can't
+//! be combined with `domain`.
+//!
+//! ### `schema_bound`
+//!
+//! Commits to the fully qualified strict type name of the type together with
+//! its semantic type id before committing to its data, such that structurally
+//! identical types from different strict type schemas never produce the same
+//! commitment (see `CommitEngine::commit_to_schema`). The id is taken from the
+//! strict type library registering the type, thus the type must implement
+//! `commit_verify::CommitmentSchema` (see
+//! `CommitEngine::commit_to_lib_schema`). Can't be combined with `domain` or
+//! `version`.
+//!
+//! ### `field_count`
+//!
+//! Makes the commitment to include the total number of the commitment steps,
+//! preventing collisions with types whose data are a prefix of the data of this
+//! type (see `CommitEngine::set_field_count_guard`). Changes the commitment
+//! value.
+//!
+//! ## Attribute arguments at enum variant level
+//!
+//! ### `strategy = strict|conceal|merklize`
+//!
+//! Specifies commitment strategy for the data of a specific enum variant. If
+//! any of the variants has this argument, each variant is committed by first
+//! committing to its strict encoding tag (see `StrictSum::ALL_VARIANTS` and
+//! `CommitEngine::commit_to_variant`), followed by the variant data committed
+//! with the variant strategy. Thus, the enum must implement `StrictSum`, and
+//! reordering of its variants doesn't change the commitment as long as their
+//! tags are preserved. Variants without the argument use the strategy
+//! provided at type declaration level.
+//!
+//! Supported only for enums where all variants are either unit or single-field
+//! tuple variants.
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate strict_encoding;
+//! # use commit_verify::{CommitEncode, StrictHash};
+//! #[derive(Clone, StrictType, StrictEncode, StrictDecode, StrictDumb)]
+//! #[strict_type(lib = "Test", tags = order)]
+//! #[derive(CommitEncode)]
+//! #[commit_encode(strategy = strict, id = StrictHash)]
+//! enum Data {
+//!     #[strict_type(dumb)]
+//!     None,
+//!     #[commit_encode(strategy = strict)]
+//!     One(StrictHash),
+//!     Two { first: StrictHash },
+//! }
+//! ```
+//!
+//! ## Attribute arguments at field level
+//!
+//! ### `signature`
+//!
+//! Marks structure field as holding a detached signature over the commitment.
+//! The rest of the fields are committed to one by one in their strict encoding
+//! (see `CommitEngine::commit_to_fields`), omitting the signature, such that
+//! the signature never affects the commitment id, and the presence of the
+//! signature slot is recorded in the commitment layout (see
+//! `CommitEngine::commit_to_detached_signature`).
+//!
+//! Requires `strategy = strict`; the field type must be a named strict type.
+//!
+//! ```
+//! # #[macro_use] extern crate strict_encoding;
+//! # use commit_verify::{CommitEncode, StrictHash};
+//! #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+//! #[strict_type(lib = "Test")]
+//! #[derive(CommitEncode)]
+//! #[commit_encode(strategy = strict, id = StrictHash)]
+//! struct Signed {
+//!     data: StrictHash,
+//!     #[commit_encode(signature)]
+//!     sig: StrictHash,
+//! }
+//! ```
+//!
+//! Other strategies do not support signature fields:
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate strict_encoding;
+//! # use commit_verify::{CommitEncode, StrictHash};
+//! #[derive(Clone, StrictType, StrictEncode, StrictDecode, StrictDumb)]
+//! #[strict_type(lib = "Test")]
+//! #[derive(CommitEncode)]
+//! #[commit_encode(strategy = conceal, id = StrictHash)]
+//! struct Signed {
+//!     data: StrictHash,
+//!     #[commit_encode(signature)]
+//!     sig: StrictHash,
+//! }
+//! ```
+//!
+//! ### `sort_by_commitment`
+//!
+//! Marks `HashSet` or `HashMap` structure field, which is committed to after
+//! the rest of the data, with its elements (or entries) ordered by the
+//! commitment ids of the elements (or keys), making the commitment
+//! deterministic (see `CommitEngine::commit_to_hash_set` and
+//! `CommitEngine::commit_to_hash_map`). Since hash collections are not strict
+//! encodable, the field must be skipped in strict encoding.
+//!
+//! Requires `strategy = strict`; the element (or key) type must implement
+//! `CommitEncode`.
+//!
+//! ```
+//! # #[macro_use] extern crate strict_encoding;
+//! # use std::collections::HashMap;
+//! # use commit_verify::{CommitEncode, StrictHash};
+//! #[derive(Clone, PartialEq, Eq, Hash, Default)]
+//! #[derive(StrictType, StrictEncode, StrictDecode)]
+//! #[strict_type(lib = "Test")]
+//! #[derive(CommitEncode)]
+//! #[commit_encode(strategy = strict, id = StrictHash)]
+//! struct Key(u16);
+//!
+//! #[derive(Clone, Default, StrictType, StrictEncode, StrictDecode)]
+//! #[strict_type(lib = "Test")]
+//! #[derive(CommitEncode)]
+//! #[commit_encode(strategy = strict, id = StrictHash)]
+//! struct State {
+//!     nonce: u8,
+//!     #[strict_type(skip)]
+//!     #[commit_encode(sort_by_commitment)]
+//!     entries: HashMap<Key, StrictHash>,
+//! }
+//! ```
+//!
+//! Other collection types are not supported:
+//!
+//! ```compile_fail
+//! # #[macro_use] extern crate strict_encoding;
+//! # use commit_verify::{CommitEncode, StrictHash};
+//! #[derive(Clone, Default, StrictType, StrictEncode, StrictDecode)]
+//! #[strict_type(lib = "Test")]
+//! #[derive(CommitEncode)]
+//! #[commit_encode(strategy = strict, id = StrictHash)]
+//! struct State {
+//!     nonce: u8,
+//!     #[strict_type(skip)]
+//!     #[commit_encode(sort_by_commitment)]
+//!     entries: Vec<StrictHash>,
+//! }
+//! ```
 
 #[macro_use]
 extern crate quote;
@@ -19,15 +19,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use amplify_syn::{ArgValueReq, AttrReq, DataType, ParametrizedAttr, TypeClass};
+use amplify_syn::{
+    ArgValueReq, AttrReq, DataInner, DataType, Field, Fields, Items, ListReq, LiteralClass,
+    ParametrizedAttr, TypeClass, Variant,
+};
 use proc_macro2::Span;
 use quote::ToTokens;
-use syn::{DeriveInput, Error, Path, Result};
+use syn::{
+    Attribute, DeriveInput, Error, Ident, Index, LitInt, Member, Meta, NestedMeta, Path, Result,
+    Type,
+};
 
 const ATTR: &str = "commit_encode";
 const ATTR_CRATE: &str = "crate";
 const ATTR_ID: &str = "id";
 const ATTR_STRATEGY: &str = "strategy";
+const ATTR_DOMAIN: &str = "domain";
+const ATTR_FIELD_COUNT: &str = "field_count";
+const ATTR_VERSION: &str = "version";
+const ATTR_SCHEMA_BOUND: &str = "schema_bound";
+const ATTR_SIGNATURE: &str = "signature";
+const ATTR_SORT_BY_COMMITMENT: &str = "sort_by_commitment";
 const ATTR_STRATEGY_STRICT: &str = "strict";
 const ATTR_STRATEGY_CONCEAL: &str = "conceal";
 const ATTR_STRATEGY_TRANSPARENT: &str = "transparent";
@@ -37,6 +49,10 @@ pub struct ContainerAttr {
     pub commit_crate: Path,
     pub strategy: StrategyAttr,
     pub id: Path,
+    pub domain: bool,
+    pub field_count: bool,
+    pub version: Option<u8>,
+    pub schema_bound: bool,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -73,16 +89,44 @@ impl TryFrom<ParametrizedAttr> for ContainerAttr {
     type Error = Error;
 
     fn try_from(mut params: ParametrizedAttr) -> Result<Self> {
-        let req = AttrReq::with(map![
+        let mut req = AttrReq::with(map![
             ATTR_CRATE => ArgValueReq::optional(TypeClass::Path),
             ATTR_ID => ArgValueReq::required(TypeClass::Path),
             ATTR_STRATEGY => ArgValueReq::required(TypeClass::Path),
+            ATTR_VERSION => ArgValueReq::optional(LiteralClass::Int),
         ]);
+        req.path_req =
+            ListReq::any_of(vec![path!(domain), path!(field_count), path!(schema_bound)], false);
         params.check(req)?;
 
         let path = params.arg_value(ATTR_STRATEGY).expect("must be present");
         let strategy = StrategyAttr::try_from(&path)?;
         let id = params.arg_value(ATTR_ID).expect("must be present");
+        let version = params
+            .arg_value::<LitInt>(ATTR_VERSION)
+            .ok()
+            .map(|lit| lit.base10_parse::<u8>())
+            .transpose()?;
+        let domain = params.has_verbatim(ATTR_DOMAIN);
+        if domain && version.is_some() {
+            return Err(Error::new(
+                Span::call_site(),
+                format!(
+                    "`{ATTR_DOMAIN}` can't be combined with `{ATTR_VERSION}`, which already \
+                     commits to the domain of the type"
+                ),
+            ));
+        }
+        let schema_bound = params.has_verbatim(ATTR_SCHEMA_BOUND);
+        if schema_bound && (domain || version.is_some()) {
+            return Err(Error::new(
+                Span::call_site(),
+                format!(
+                    "`{ATTR_SCHEMA_BOUND}` can't be combined with `{ATTR_DOMAIN}` or \
+                     `{ATTR_VERSION}`, which also must be the first commitment step"
+                ),
+            ));
+        }
 
         Ok(ContainerAttr {
             commit_crate: params
@@ -90,13 +134,171 @@ impl TryFrom<ParametrizedAttr> for ContainerAttr {
                 .unwrap_or_else(|_| path!(commit_verify)),
             strategy,
             id,
+            domain,
+            field_count: params.has_verbatim(ATTR_FIELD_COUNT),
+            version,
+            schema_bound,
         })
     }
 }
 
+/// Structure field holding a detached signature, which is excluded from the
+/// commitment.
+pub struct SignatureField {
+    pub ty: Type,
+}
+
+/// Kind of unordered collection committed in the order of the commitment ids
+/// of its elements or keys.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SortedKind {
+    HashSet,
+    HashMap,
+}
+
+/// Structure field holding unordered collection, which is committed to
+/// separately from the rest of the data.
+pub struct SortedField {
+    pub member: Member,
+    pub kind: SortedKind,
+}
+
+/// Parses field-level attributes, returning signature and sorted collection
+/// fields.
+fn parse_field(
+    member: Member,
+    field: &Field,
+) -> Result<(Option<SignatureField>, Option<SortedField>)> {
+    let mut attr = field.attr.clone();
+    let mut req = AttrReq::with(none!());
+    req.path_req = ListReq::any_of(vec![path!(signature), path!(sort_by_commitment)], false);
+    attr.check(req)?;
+
+    let signature = attr.has_verbatim(ATTR_SIGNATURE);
+    let sorted = attr.has_verbatim(ATTR_SORT_BY_COMMITMENT);
+    if signature && sorted {
+        return Err(Error::new(
+            Span::call_site(),
+            format!(
+                "`{ATTR_SIGNATURE}` and `{ATTR_SORT_BY_COMMITMENT}` can't be used for the same \
+                 field"
+            ),
+        ));
+    }
+
+    let signature = signature.then(|| SignatureField {
+        ty: field.ty.clone(),
+    });
+    if !sorted {
+        return Ok((signature, None));
+    }
+    let kind = match &field.ty {
+        Type::Path(path) => match path.path.segments.last() {
+            Some(segment) if segment.ident == "HashSet" => Some(SortedKind::HashSet),
+            Some(segment) if segment.ident == "HashMap" => Some(SortedKind::HashMap),
+            _ => None,
+        },
+        _ => None,
+    };
+    let Some(kind) = kind else {
+        return Err(Error::new(
+            Span::call_site(),
+            format!(
+                "`{ATTR_SORT_BY_COMMITMENT}` is supported only for `HashSet` and `HashMap` fields"
+            ),
+        ));
+    };
+    Ok((signature, Some(SortedField { member, kind })))
+}
+
+/// Enum variant committed with its own strategy.
+pub struct VariantStrategy {
+    pub name: Ident,
+    pub strategy: StrategyAttr,
+    pub unit: bool,
+}
+
+impl VariantStrategy {
+    /// Parses per-variant strategies, returning empty list if none of the
+    /// variants has its own strategy.
+    fn parse_all(items: &Items<Variant>, default: StrategyAttr) -> Result<Vec<Self>> {
+        let mut custom = false;
+        let mut variants = vec![];
+        for variant in items {
+            let mut attr = variant.attr.clone();
+            attr.check(AttrReq::with(map![
+                ATTR_STRATEGY => ArgValueReq::optional(TypeClass::Path),
+            ]))?;
+            let strategy = match attr.arg_value::<Path>(ATTR_STRATEGY) {
+                Ok(path) => {
+                    custom = true;
+                    StrategyAttr::try_from(&path)?
+                }
+                Err(_) => default,
+            };
+            let unit = match &variant.fields {
+                Fields::Unit => Some(true),
+                Fields::Unnamed(fields) if fields.len() == 1 => Some(false),
+                _ => None,
+            };
+            variants.push((variant.name.clone(), strategy, unit));
+        }
+        if !custom {
+            return Ok(vec![]);
+        }
+
+        variants
+            .into_iter()
+            .map(|(name, strategy, unit)| {
+                let Some(unit) = unit else {
+                    return Err(Error::new(
+                        name.span(),
+                        "per-variant commitment strategies require unit or single-field tuple \
+                         variants",
+                    ));
+                };
+                if strategy == StrategyAttr::Transparent {
+                    return Err(Error::new(
+                        name.span(),
+                        format!(
+                            "`{ATTR_STRATEGY_TRANSPARENT}` strategy can't be used for enum \
+                             variants"
+                        ),
+                    ));
+                }
+                Ok(VariantStrategy {
+                    name,
+                    strategy,
+                    unit,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Checks whether the field is skipped in strict encoding with
+/// `#[strict_type(skip)]`.
+fn is_strict_skipped(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("strict_type"))
+        .any(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested.iter().any(
+                |nested| matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip")),
+            ),
+            _ => false,
+        })
+}
+
 pub struct CommitDerive {
     pub data: DataType,
     pub conf: ContainerAttr,
+    /// Structure fields which are strict-serialized, excluding signature
+    /// fields.
+    pub serialized: Vec<Member>,
+    pub signatures: Vec<SignatureField>,
+    pub sorted: Vec<SortedField>,
+    pub variants: Vec<VariantStrategy>,
 }
 
 impl TryFrom<DeriveInput> for CommitDerive {
@@ -104,8 +306,75 @@ impl TryFrom<DeriveInput> for CommitDerive {
 
     fn try_from(input: DeriveInput) -> Result<Self> {
         let params = ParametrizedAttr::with(ATTR, &input.attrs)?;
+        let strict_skipped = match &input.data {
+            syn::Data::Struct(data) => data
+                .fields
+                .iter()
+                .map(|field| is_strict_skipped(&field.attrs))
+                .collect(),
+            _ => vec![],
+        };
         let conf = ContainerAttr::try_from(params)?;
         let data = DataType::with(input, ident!(commit_encode))?;
-        Ok(Self { data, conf })
+
+        let mut signatures = vec![];
+        let mut sorted = vec![];
+        let mut members = vec![];
+        match &data.inner {
+            DataInner::Struct(Fields::Named(fields)) => {
+                for named in fields {
+                    members.push((Member::Named(named.name.clone()), &named.field));
+                }
+            }
+            DataInner::Struct(Fields::Unnamed(fields)) => {
+                for (index, field) in fields.iter().enumerate() {
+                    members.push((Member::Unnamed(Index::from(index)), field));
+                }
+            }
+            _ => {}
+        }
+        let mut serialized = vec![];
+        for ((member, field), skipped) in members.into_iter().zip(strict_skipped) {
+            let (signature, sorted_field) = parse_field(member.clone(), field)?;
+            if signature.is_none() && !skipped {
+                serialized.push(member);
+            }
+            signatures.extend(signature);
+            sorted.extend(sorted_field);
+        }
+
+        let variants = match &data.inner {
+            DataInner::Enum(items) => VariantStrategy::parse_all(items, conf.strategy)?,
+            _ => vec![],
+        };
+
+        if !signatures.is_empty() && conf.strategy != StrategyAttr::Strict {
+            return Err(Error::new(
+                Span::call_site(),
+                format!(
+                    "`{ATTR_SIGNATURE}` fields are supported only with `{ATTR_STRATEGY} = \
+                     {ATTR_STRATEGY_STRICT}`"
+                ),
+            ));
+        }
+
+        if !sorted.is_empty() && conf.strategy != StrategyAttr::Strict {
+            return Err(Error::new(
+                Span::call_site(),
+                format!(
+                    "`{ATTR_SORT_BY_COMMITMENT}` fields are supported only with `{ATTR_STRATEGY} \
+                     = {ATTR_STRATEGY_STRICT}`"
+                ),
+            ));
+        }
+
+        Ok(Self {
+            data,
+            conf,
+            serialized,
+            signatures,
+            sorted,
+            variants,
+        })
     }
 }
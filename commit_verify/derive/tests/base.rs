@@ -28,11 +28,19 @@ extern crate strict_encoding;
 
 mod common;
 
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
+use std::marker::PhantomData;
+use std::sync::OnceLock;
 
 use amplify::{Bytes32, Wrapper};
-use commit_verify::{CommitEncode, CommitId, CommitmentId, Conceal, DigestExt, Sha256};
+use commit_verify::{
+    type_sem_id, CommitEncode, CommitEngine, CommitId, CommitStep, CommitmentId, CommitmentLayout,
+    CommitmentSchema, Conceal, DigestExt, Sha256,
+};
 use strict_encoding::{StrictDecode, StrictDumb, StrictEncode};
+use strict_types::stl::std_stl;
+use strict_types::{LibBuilder, TypeLib};
 
 const TEST_LIB: &str = "TestLib";
 
@@ -138,6 +146,486 @@ fn strategy_strict_struct() -> common::Result {
     Ok(())
 }
 
+#[test]
+fn phantom() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId)]
+    struct Info {
+        a: u16,
+        b: u64,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "Info")]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId)]
+    struct TaggedInfo<T> {
+        a: u16,
+        b: u64,
+        marker: PhantomData<T>,
+    }
+
+    let info = Info {
+        a: 0xdead,
+        b: 0xbeefcafebaddafec,
+    };
+    let tagged = TaggedInfo::<u128> {
+        a: 0xdead,
+        b: 0xbeefcafebaddafec,
+        marker: PhantomData,
+    };
+    assert_eq!(tagged.commit_id(), info.commit_id());
+    assert_eq!(tagged.commit().into_layout(), info.commit().into_layout());
+
+    Ok(())
+}
+
+#[test]
+fn domain() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId, domain)]
+    struct TaggedInfo {
+        a: u16,
+        b: u64,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "TaggedInfo")]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId)]
+    struct UntaggedInfo {
+        a: u16,
+        b: u64,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId, domain)]
+    struct OtherInfo {
+        a: u16,
+        b: u64,
+    }
+
+    let info = TaggedInfo {
+        a: 0xdead,
+        b: 0xbeefcafebaddafec,
+    };
+    let other = OtherInfo {
+        a: 0xdead,
+        b: 0xbeefcafebaddafec,
+    };
+    let untagged = UntaggedInfo {
+        a: 0xdead,
+        b: 0xbeefcafebaddafec,
+    };
+    assert_ne!(info.commit_id(), other.commit_id());
+    assert_ne!(info.commit_id(), untagged.commit_id());
+
+    let mut engine = CommitEngine::new(DumbId::TAG);
+    engine.commit_to_domain::<TaggedInfo>();
+    engine.commit_to_serialized(&untagged);
+    assert_eq!(info.commit_id(), DumbId::from(engine.finish()));
+
+    let layout = TaggedInfo::commitment_layout();
+    let CommitStep::Domain(fqn) = &layout.fields()[0] else {
+        panic!("domain must be the first commitment step");
+    };
+    assert_eq!(fqn.to_string(), format!("{TEST_LIB}.TaggedInfo"));
+
+    Ok(())
+}
+
+#[test]
+fn version() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "Info")]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId, version = 1)]
+    struct InfoV1 {
+        a: u16,
+        b: u64,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "Info")]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId, version = 2)]
+    struct InfoV2 {
+        a: u16,
+        b: u64,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "Info")]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId)]
+    struct Info {
+        a: u16,
+        b: u64,
+    }
+
+    assert_eq!(InfoV1::COMMITMENT_VERSION, 1);
+    assert_eq!(InfoV2::COMMITMENT_VERSION, 2);
+    assert_eq!(Info::COMMITMENT_VERSION, 0);
+
+    let v1 = InfoV1 {
+        a: 0xdead,
+        b: 0xbeefcafebaddafec,
+    };
+    let v2 = InfoV2 {
+        a: 0xdead,
+        b: 0xbeefcafebaddafec,
+    };
+    let plain = Info {
+        a: 0xdead,
+        b: 0xbeefcafebaddafec,
+    };
+    assert_ne!(v1.commit_id(), v2.commit_id());
+    assert_ne!(v1.commit_id(), plain.commit_id());
+
+    let layout = InfoV1::commitment_layout();
+    assert!(matches!(layout.fields()[0], CommitStep::Version(_, 1)));
+
+    Ok(())
+}
+
+#[test]
+fn schema_bound() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "Point")]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId, schema_bound)]
+    struct Cartesian {
+        x: u16,
+        y: u64,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "Point")]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId, schema_bound)]
+    struct Polar {
+        rho: u16,
+        phi: u64,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "Point")]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId, domain)]
+    struct CartesianDomain {
+        x: u16,
+        y: u64,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "Point")]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId, domain)]
+    struct PolarDomain {
+        rho: u16,
+        phi: u64,
+    }
+
+    fn lib<T: StrictEncode + StrictDumb>() -> TypeLib {
+        LibBuilder::new(libname!(TEST_LIB), tiny_bset! { std_stl().to_dependency() })
+            .transpile::<T>()
+            .compile()
+            .unwrap()
+    }
+    impl CommitmentSchema for Cartesian {
+        fn commitment_lib() -> &'static TypeLib {
+            static LIB: OnceLock<TypeLib> = OnceLock::new();
+            LIB.get_or_init(lib::<Self>)
+        }
+    }
+    impl CommitmentSchema for Polar {
+        fn commitment_lib() -> &'static TypeLib {
+            static LIB: OnceLock<TypeLib> = OnceLock::new();
+            LIB.get_or_init(lib::<Self>)
+        }
+    }
+
+    let cartesian = Cartesian {
+        x: 0xdead,
+        y: 0xbeefcafebaddafec,
+    };
+    let polar = Polar {
+        rho: 0xdead,
+        phi: 0xbeefcafebaddafec,
+    };
+    assert_ne!(cartesian.commit_id(), polar.commit_id());
+    assert_eq!(cartesian.commit_id(), cartesian.clone().commit_id());
+
+    // Types differing only by their strict type ids can't be distinguished by
+    // their domain
+    let cartesian = CartesianDomain {
+        x: 0xdead,
+        y: 0xbeefcafebaddafec,
+    };
+    let polar = PolarDomain {
+        rho: 0xdead,
+        phi: 0xbeefcafebaddafec,
+    };
+    assert_eq!(cartesian.commit_id(), polar.commit_id());
+
+    let cartesian = Cartesian::commitment_layout();
+    let polar = Polar::commitment_layout();
+    let (CommitStep::Schema(cartesian_fqn, cartesian_id), CommitStep::Schema(polar_fqn, polar_id)) =
+        (&cartesian.fields()[0], &polar.fields()[0])
+    else {
+        panic!("schema must be the first commitment step");
+    };
+    assert_eq!(cartesian_fqn, polar_fqn);
+    assert_ne!(cartesian_id, polar_id);
+    assert_eq!(Some(*cartesian_id), type_sem_id::<Cartesian>(Cartesian::commitment_lib()));
+    assert_eq!(Some(*polar_id), type_sem_id::<Polar>(Polar::commitment_lib()));
+
+    Ok(())
+}
+
+#[test]
+fn field_count() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId)]
+    struct Plain {
+        a: u16,
+        b: u64,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "Plain")]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId, field_count)]
+    struct Guarded {
+        a: u16,
+        b: u64,
+    }
+
+    let plain = Plain {
+        a: 0xdead,
+        b: 0xbeefcafebaddafec,
+    };
+    let guarded = Guarded {
+        a: 0xdead,
+        b: 0xbeefcafebaddafec,
+    };
+    assert_ne!(plain.commit_id(), guarded.commit_id());
+
+    Ok(())
+}
+
+#[test]
+fn signature() -> common::Result {
+    #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Sig(u64);
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId)]
+    struct Signed {
+        a: u16,
+        b: u64,
+        #[commit_encode(signature)]
+        sig: Sig,
+    }
+
+    let unsigned = Signed {
+        a: 0xdead,
+        b: 0xbeefcafebaddafec,
+        sig: Sig::default(),
+    };
+    let signed = Signed {
+        sig: Sig(0xcafe),
+        ..unsigned.clone()
+    };
+    assert_eq!(unsigned.commit_id(), signed.commit_id());
+    assert_ne!(
+        Signed {
+            a: 0xbeef,
+            ..signed.clone()
+        }
+        .commit_id(),
+        signed.commit_id()
+    );
+
+    // The signature is omitted from the commitment
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "Signed")]
+    struct Unsigned {
+        a: u16,
+        b: u64,
+    }
+    let mut engine = CommitEngine::new(DumbId::TAG);
+    engine.commit_to_serialized(&Unsigned {
+        a: signed.a,
+        b: signed.b,
+    });
+    assert_eq!(signed.commit_id(), DumbId::from(engine.finish()));
+
+    let layout = Signed::commitment_layout();
+    assert_eq!(layout.fields().len(), 2);
+    assert!(matches!(layout.fields()[1], CommitStep::DetachedSignature(_)));
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, rename = "Signed")]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId)]
+    struct SignedCached {
+        a: u16,
+        #[strict_type(skip)]
+        cache: u32,
+        b: u64,
+        #[commit_encode(signature)]
+        sig: Sig,
+    }
+
+    let cached = SignedCached {
+        a: signed.a,
+        cache: 0xcafe,
+        b: signed.b,
+        sig: Sig(0xbeef),
+    };
+    assert_eq!(cached.commit_id(), signed.commit_id());
+
+    Ok(())
+}
+
+#[test]
+fn sort_by_commitment() -> common::Result {
+    #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId)]
+    struct Key(u16);
+
+    #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Value(u64);
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId)]
+    struct State {
+        nonce: u8,
+        #[strict_type(skip)]
+        #[commit_encode(sort_by_commitment)]
+        keys: HashSet<Key>,
+        #[strict_type(skip)]
+        #[commit_encode(sort_by_commitment)]
+        values: HashMap<Key, Value>,
+    }
+
+    let mut state1 = State::default();
+    let mut state2 = State {
+        keys: HashSet::with_capacity(100),
+        values: HashMap::with_capacity(100),
+        ..default!()
+    };
+    for no in 0..20u16 {
+        state1.keys.insert(Key(no));
+        state1.values.insert(Key(no), Value(no as u64 * 3));
+        state2.keys.insert(Key(19 - no));
+        state2
+            .values
+            .insert(Key(19 - no), Value((19 - no) as u64 * 3));
+    }
+    assert_eq!(state1, state2);
+    assert_eq!(state1.commit_id(), state2.commit_id());
+
+    state2.values.insert(Key(0), Value(1));
+    assert_ne!(state1.commit_id(), state2.commit_id());
+    state2.values.insert(Key(0), Value(0));
+    state2.keys.remove(&Key(5));
+    assert_ne!(state1.commit_id(), state2.commit_id());
+
+    let layout = State::commitment_layout();
+    assert_eq!(layout.fields().len(), 3);
+    assert!(matches!(layout.fields()[1], CommitStep::Collection(..)));
+
+    // Distinct elements with the same commitment id are committed in a
+    // deterministic order
+    #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Labeled {
+        id: u16,
+        label: u8,
+    }
+    impl CommitEncode for Labeled {
+        type CommitmentId = DumbId;
+        fn commit_encode(&self, engine: &mut CommitEngine) {
+            // The label doesn't contribute to the commitment id
+            engine.commit_to_serialized(&self.id);
+        }
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Default)]
+    #[derive(StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId)]
+    struct Labels {
+        #[strict_type(skip)]
+        #[commit_encode(sort_by_commitment)]
+        keys: HashSet<Labeled>,
+        #[strict_type(skip)]
+        #[commit_encode(sort_by_commitment)]
+        values: HashMap<Labeled, Value>,
+    }
+
+    let labels = |order: &[u8]| {
+        let mut labels = Labels::default();
+        for label in order {
+            let labeled = Labeled {
+                id: 1,
+                label: *label,
+            };
+            labels.keys.insert(labeled);
+            labels.values.insert(labeled, Value(*label as u64));
+        }
+        labels.commit_id()
+    };
+    let id = labels(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    for _ in 0..16 {
+        assert_eq!(labels(&[8, 7, 6, 5, 4, 3, 2, 1]), id);
+        assert_eq!(labels(&[5, 1, 7, 3, 8, 2, 6, 4]), id);
+    }
+
+    Ok(())
+}
+
 #[test]
 fn enum_associated() -> common::Result {
     #[allow(dead_code)]
@@ -228,6 +716,87 @@ fn conceal() -> common::Result {
     Ok(())
 }
 
+#[test]
+fn variant_strategies() -> common::Result {
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Amount(u64);
+
+    impl Conceal for Amount {
+        type Concealed = Self;
+        fn conceal(&self) -> Self { Amount(0) }
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB)]
+    struct Script(u16);
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, tags = order, dumb = { Self::Burn })]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId)]
+    enum Transition {
+        #[commit_encode(strategy = conceal)]
+        Transfer(Amount),
+        Issue(Script),
+        Burn,
+    }
+
+    let mut engine = CommitEngine::new(DumbId::TAG);
+    engine.commit_to_variant::<Transition>(0);
+    engine.commit_to_concealed(&Amount(1));
+    assert_eq!(Transition::Transfer(Amount(1)).commit_id(), DumbId::from(engine.finish()));
+
+    let mut engine = CommitEngine::new(DumbId::TAG);
+    engine.commit_to_variant::<Transition>(1);
+    engine.commit_to_serialized(&Script(0xdead));
+    assert_eq!(Transition::Issue(Script(0xdead)).commit_id(), DumbId::from(engine.finish()));
+
+    let mut engine = CommitEngine::new(DumbId::TAG);
+    engine.commit_to_variant::<Transition>(2);
+    assert_eq!(Transition::Burn.commit_id(), DumbId::from(engine.finish()));
+
+    assert_eq!(
+        Transition::Transfer(Amount(1)).commit_id(),
+        Transition::Transfer(Amount(2)).commit_id()
+    );
+    assert_ne!(Transition::Issue(Script(1)).commit_id(), Transition::Issue(Script(2)).commit_id());
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+    #[strict_type(lib = TEST_LIB, tags = custom, dumb = { Self::Burn })]
+    #[derive(CommitEncode)]
+    #[commit_encode(strategy = strict, id = DumbId)]
+    enum Tagged {
+        #[strict_type(tag = 0x10)]
+        Burn,
+        #[strict_type(tag = 0x20)]
+        Issue(Script),
+        #[commit_encode(strategy = conceal)]
+        #[strict_type(tag = 0x30)]
+        Transfer(Amount),
+    }
+
+    let mut engine = CommitEngine::new(DumbId::TAG);
+    engine.commit_to_variant::<Tagged>(0x30);
+    engine.commit_to_concealed(&Amount(1));
+    assert_eq!(Tagged::Transfer(Amount(1)).commit_id(), DumbId::from(engine.finish()));
+
+    let mut engine = CommitEngine::new(DumbId::TAG);
+    engine.commit_to_variant::<Tagged>(0x20);
+    engine.commit_to_serialized(&Script(0xdead));
+    assert_eq!(Tagged::Issue(Script(0xdead)).commit_id(), DumbId::from(engine.finish()));
+
+    let mut engine = CommitEngine::new(DumbId::TAG);
+    engine.commit_to_variant::<Tagged>(0x10);
+    assert_eq!(Tagged::Burn.commit_id(), DumbId::from(engine.finish()));
+
+    Ok(())
+}
+
 /* TODO: Refactor
 #[test]
 fn merklize() -> common::Result {
@@ -0,0 +1,191 @@
+// Client-side-validation foundation libraries.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! XOR-based accumulator for unordered sets.
+
+use amplify::Bytes32;
+use sha2::Sha256;
+
+use crate::{CommitEncode, CommitId, DigestExt, StrictHash};
+
+/// Tag used for hashing accumulator elements.
+pub const XOR_ELEMENT_TAG: &str = "urn:ubideco:xor-accumulator:element#2026-10-14";
+/// Tag used for producing accumulator root.
+pub const XOR_ROOT_TAG: &str = "urn:ubideco:xor-accumulator:root#2026-10-14";
+
+fn element_hash(id: StrictHash) -> [u8; 32] {
+    let mut engine = Sha256::from_tag(XOR_ELEMENT_TAG);
+    engine.input_raw(id.as_slice());
+    engine.finish()
+}
+
+fn xor(acc: &mut [u8; 32], hash: [u8; 32]) { acc.iter_mut().zip(hash).for_each(|(a, b)| *a ^= b); }
+
+fn root(acc: [u8; 32], count: u64) -> StrictHash {
+    let mut engine = Sha256::from_tag(XOR_ROOT_TAG);
+    engine.input_raw(&acc);
+    engine.input_u64_le(count);
+    StrictHash::from(Bytes32::from_array(engine.finish()))
+}
+
+/// Accumulator of an unordered set of elements, folding tagged hashes of each
+/// element's [`StrictHash`] commitment id with XOR.
+///
+/// Insertion and removal are O(1) and commutative, such that the root doesn't
+/// depend on the order of insertion. The root commits to the XOR of all the
+/// element hashes and the number of the elements.
+///
+/// # Security
+///
+/// The accumulator provides much weaker guarantees than merkle trees and must
+/// not be used where elements may be chosen by an adversary:
+/// - XOR of hashes is susceptible to generalized birthday attacks, allowing to
+///   find a set of elements producing a given accumulator value with much less
+///   work than finding a hash collision;
+/// - the same element inserted twice cancels itself out (though the element
+///   count is still committed to);
+/// - removal of an element which was never inserted is not detected;
+/// - there are no non-membership proofs.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct XorAccumulator {
+    acc: [u8; 32],
+    count: u64,
+}
+
+impl XorAccumulator {
+    /// Constructs empty accumulator.
+    pub fn new() -> Self { Self::default() }
+
+    /// Number of the elements in the accumulator.
+    pub fn len(&self) -> u64 { self.count }
+
+    /// Detects whether the accumulator contains no elements.
+    pub fn is_empty(&self) -> bool { self.count == 0 }
+
+    /// Adds an element to the accumulator.
+    pub fn insert(&mut self, item: &impl CommitEncode<CommitmentId = StrictHash>) {
+        xor(&mut self.acc, element_hash(item.commit_id()));
+        self.count += 1;
+    }
+
+    /// Removes a previously inserted element from the accumulator.
+    ///
+    /// # Panics
+    ///
+    /// If the accumulator is empty.
+    pub fn remove(&mut self, item: &impl CommitEncode<CommitmentId = StrictHash>) {
+        assert!(self.count > 0, "removing element from an empty accumulator");
+        xor(&mut self.acc, element_hash(item.commit_id()));
+        self.count -= 1;
+    }
+
+    /// Computes the root commitment of the accumulator.
+    pub fn root(&self) -> StrictHash { root(self.acc, self.count) }
+
+    /// Constructs membership witness for the `item`, which must be already
+    /// inserted into the accumulator.
+    pub fn witness(&self, item: &impl CommitEncode<CommitmentId = StrictHash>) -> XorWitness {
+        let mut rest = self.acc;
+        xor(&mut rest, element_hash(item.commit_id()));
+        XorWitness {
+            rest,
+            count: self.count,
+        }
+    }
+}
+
+/// Witness of an element membership in [`XorAccumulator`], containing the
+/// accumulated value of all other elements.
+///
+/// Since anybody knowing the accumulated value can construct a valid witness
+/// for an arbitrary element, the witness proves membership only when provided
+/// by a party trusted to maintain the accumulator honestly.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct XorWitness {
+    rest: [u8; 32],
+    count: u64,
+}
+
+impl XorWitness {
+    /// Verifies that the `item` is a member of the accumulator with the given
+    /// `root`.
+    pub fn verify(
+        &self,
+        item: &impl CommitEncode<CommitmentId = StrictHash>,
+        root_commitment: StrictHash,
+    ) -> bool {
+        let mut acc = self.rest;
+        xor(&mut acc, element_hash(item.commit_id()));
+        self.count > 0 && root(acc, self.count) == root_commitment
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{CommitEngine, LIB_NAME_COMMIT_VERIFY};
+
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+    #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+    struct Item(u32);
+
+    impl CommitEncode for Item {
+        type CommitmentId = StrictHash;
+        fn commit_encode(&self, e: &mut CommitEngine) { e.commit_to_serialized(self); }
+    }
+
+    #[test]
+    fn order_independence() {
+        let mut acc1 = XorAccumulator::new();
+        let mut acc2 = XorAccumulator::new();
+        for no in 0..5 {
+            acc1.insert(&Item(no));
+            acc2.insert(&Item(4 - no));
+        }
+        assert_eq!(acc1.len(), 5);
+        assert_eq!(acc1.root(), acc2.root());
+        assert_ne!(acc1.root(), XorAccumulator::new().root());
+    }
+
+    #[test]
+    fn remove() {
+        let mut acc = XorAccumulator::new();
+        acc.insert(&Item(1));
+        let root = acc.root();
+        acc.insert(&Item(2));
+        assert_ne!(acc.root(), root);
+        acc.remove(&Item(2));
+        assert_eq!(acc.root(), root);
+        acc.remove(&Item(1));
+        assert!(acc.is_empty());
+        assert_eq!(acc.root(), XorAccumulator::new().root());
+    }
+
+    #[test]
+    fn membership() {
+        let mut acc = XorAccumulator::new();
+        (0..3).for_each(|no| acc.insert(&Item(no)));
+        let witness = acc.witness(&Item(1));
+        assert!(witness.verify(&Item(1), acc.root()));
+        assert!(!witness.verify(&Item(7), acc.root()));
+    }
+}
@@ -21,7 +21,9 @@
 
 //! Base commit-verify scheme interface.
 
-use crate::CommitmentProtocol;
+use std::marker::PhantomData;
+
+use crate::{CommitEncode, CommitmentProtocol};
 
 /// Error during commitment verification
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Display, Error)]
@@ -77,6 +79,119 @@ where Self: Eq + Sized
     }
 }
 
+/// Commitment protocol carrying runtime context data, like a network
+/// identifier, which makes commitments to the same message under different
+/// contexts distinct.
+///
+/// The context must be deterministic: the same protocol value must always
+/// provide the same context bytes, and all parties creating and verifying a
+/// commitment must use byte-to-byte equal context.
+pub trait ContextualProtocol: CommitmentProtocol {
+    /// Context data folded into the commitment.
+    fn context(&self) -> &[u8];
+}
+
+/// Trait for commit-verify scheme where the commitment depends on the runtime
+/// context provided by the protocol.
+pub trait ContextualCommit<Msg, Protocol: ContextualProtocol>
+where Self: Eq + Sized
+{
+    /// Creates a commitment to a message under the context of the `protocol`.
+    fn commit_in(protocol: &Protocol, msg: &Msg) -> Self;
+
+    /// Verifies commitment against the message under the context of the
+    /// `protocol`; default implementation just repeats the commitment to the
+    /// message and check it against the `self`.
+    #[inline]
+    fn verify_in(&self, protocol: &Protocol, msg: &Msg) -> Result<(), VerifyError> {
+        match Self::commit_in(protocol, msg) == *self {
+            false => Err(VerifyError::InvalidCommitment),
+            true => Ok(()),
+        }
+    }
+}
+
+/// Contextual protocol folding the magic bytes of a blockchain network into the
+/// commitment, such that a commitment created for one network can't be
+/// replayed on another.
+///
+/// Constants for the bitcoin networks use the message start bytes of the
+/// bitcoin P2P protocol.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct NetworkTaggedProtocol([u8; 4]);
+
+impl NetworkTaggedProtocol {
+    /// Bitcoin mainnet.
+    pub const BITCOIN: Self = Self([0xF9, 0xBE, 0xB4, 0xD9]);
+    /// Bitcoin testnet3.
+    pub const TESTNET3: Self = Self([0x0B, 0x11, 0x09, 0x07]);
+    /// Bitcoin testnet4.
+    pub const TESTNET4: Self = Self([0x1C, 0x16, 0x3F, 0x28]);
+    /// Bitcoin signet.
+    pub const SIGNET: Self = Self([0x0A, 0x03, 0xCF, 0x40]);
+    /// Bitcoin regtest.
+    pub const REGTEST: Self = Self([0xFA, 0xBF, 0xB5, 0xDA]);
+
+    /// Constructs protocol for a network with the given magic bytes.
+    pub const fn with_magic(magic: [u8; 4]) -> Self { Self(magic) }
+
+    /// Returns magic bytes of the network.
+    pub const fn magic(&self) -> [u8; 4] { self.0 }
+}
+
+impl CommitmentProtocol for NetworkTaggedProtocol {}
+
+impl ContextualProtocol for NetworkTaggedProtocol {
+    fn context(&self) -> &[u8] { &self.0 }
+}
+
+/// Commits to the message for a specific `network`, such that the commitment
+/// differs from the commitments to the same message for other networks.
+pub fn commit_for_network<T: CommitEncode>(
+    msg: &T,
+    network: NetworkTaggedProtocol,
+) -> T::CommitmentId {
+    T::CommitmentId::commit_in(&network, msg)
+}
+
+/// Object-safe commit-verify scheme over byte messages, allowing to use
+/// different commitment schemes behind a trait object.
+///
+/// Commit-verify schemes defined by [`CommitVerify`] can be used via
+/// [`DynScheme`] adaptor.
+pub trait DynCommit {
+    /// Creates a commitment to the message, returning its byte
+    /// representation.
+    fn commit_dyn(&self, msg: &[u8]) -> Box<[u8]>;
+
+    /// Verifies the byte representation of a commitment against the message;
+    /// default implementation just repeats the commitment to the message and
+    /// compares the bytes.
+    fn verify_dyn(&self, commitment: &[u8], msg: &[u8]) -> bool {
+        *self.commit_dyn(msg) == *commitment
+    }
+}
+
+/// Adaptor exposing the [`CommitVerify`] scheme with commitment type `C` and
+/// protocol `P` over byte messages as a [`DynCommit`] object.
+pub struct DynScheme<C, P>(PhantomData<(C, P)>);
+
+impl<C, P> Default for DynScheme<C, P> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<C, P> DynScheme<C, P> {
+    pub const fn new() -> Self { Self(PhantomData) }
+}
+
+impl<C, P> DynCommit for DynScheme<C, P>
+where
+    C: CommitVerify<Vec<u8>, P> + AsRef<[u8]>,
+    P: CommitmentProtocol,
+{
+    fn commit_dyn(&self, msg: &[u8]) -> Box<[u8]> { C::commit(&msg.to_vec()).as_ref().into() }
+}
+
 /// Helpers for writing test functions working with commit-verify scheme
 #[cfg(test)]
 pub(crate) mod test_helpers {
@@ -127,3 +242,51 @@ pub(crate) mod test_helpers {
             });
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Digest, Sha256, UntaggedProtocol};
+
+    #[derive(Clone, Eq, PartialEq, Debug)]
+    struct HashCommitment([u8; 32]);
+
+    impl CommitVerify<Vec<u8>, UntaggedProtocol> for HashCommitment {
+        fn commit(msg: &Vec<u8>) -> Self { Self(Sha256::digest(msg).into()) }
+    }
+
+    impl AsRef<[u8]> for HashCommitment {
+        fn as_ref(&self) -> &[u8] { &self.0 }
+    }
+
+    #[derive(Clone, Eq, PartialEq, Debug)]
+    struct XorCommitment([u8; 1]);
+
+    impl CommitVerify<Vec<u8>, UntaggedProtocol> for XorCommitment {
+        fn commit(msg: &Vec<u8>) -> Self { Self([msg.iter().fold(0, |acc, byte| acc ^ byte)]) }
+    }
+
+    impl AsRef<[u8]> for XorCommitment {
+        fn as_ref(&self) -> &[u8] { &self.0 }
+    }
+
+    #[test]
+    fn dyn_commit() {
+        let registry: Vec<Box<dyn DynCommit>> = vec![
+            Box::new(DynScheme::<HashCommitment, UntaggedProtocol>::new()),
+            Box::new(DynScheme::<XorCommitment, UntaggedProtocol>::new()),
+        ];
+        let msg = b"message";
+
+        let hash = registry[0].commit_dyn(msg);
+        assert_eq!(&*hash, HashCommitment::commit(&msg.to_vec()).as_ref());
+        let xor = registry[1].commit_dyn(msg);
+        assert_eq!(xor.len(), 1);
+
+        assert!(registry[0].verify_dyn(&hash, msg));
+        assert!(registry[1].verify_dyn(&xor, msg));
+        assert!(!registry[0].verify_dyn(&xor, msg));
+        assert!(!registry[1].verify_dyn(&hash, msg));
+        assert!(!registry[0].verify_dyn(&hash, b"other"));
+    }
+}
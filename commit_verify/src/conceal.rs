@@ -19,6 +19,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+use std::hash::Hash;
+
+use amplify::confinement::Confined;
+
+use crate::{CommitEncode, CommitId, StrictHash};
+
 /// Trait that should perform conversion of a given client-side-validated data
 /// type into a concealed (private) form, for instance hiding some of the data
 /// behind hashed - or homomorphically-encrypted version.
@@ -34,3 +41,170 @@ pub trait Conceal {
     /// original data.
     fn conceal(&self) -> Self::Concealed;
 }
+
+/// Helper concealing arbitrary commit-encodable data into their [`StrictHash`]
+/// commitment. Useful for implementing [`Conceal`] for types with fields which
+/// don't have own concealed form.
+///
+/// ```ignore
+/// impl Conceal for Contract {
+///     type Concealed = ContractConcealed;
+///
+///     fn conceal(&self) -> Self::Concealed {
+///         ContractConcealed {
+///             terms: ConcealByHash::conceal(&self.terms),
+///             ...
+///         }
+///     }
+/// }
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ConcealByHash;
+
+impl ConcealByHash {
+    /// Conceals the value by computing its [`StrictHash`] commitment id.
+    pub fn conceal<T>(value: &T) -> StrictHash
+    where T: CommitEncode<CommitmentId = StrictHash> {
+        value.commit_id()
+    }
+}
+
+/// Trait for data structures containing multiple concealable values, which may
+/// be concealed in-place all at once.
+///
+/// Implementations must conceal every revealed value reachable from the
+/// structure, such that a single call to [`ConcealAll::conceal_all`] fully
+/// conceals the structure.
+pub trait ConcealAll {
+    /// Conceals all revealed values reachable from `self` in-place.
+    ///
+    /// # Returns
+    ///
+    /// Number of values which were concealed by the call (values which were
+    /// already concealed are not counted).
+    fn conceal_all(&mut self) -> usize;
+}
+
+impl<T: ConcealAll + ?Sized> ConcealAll for Box<T> {
+    fn conceal_all(&mut self) -> usize { self.as_mut().conceal_all() }
+}
+
+impl<T: ConcealAll> ConcealAll for Option<T> {
+    fn conceal_all(&mut self) -> usize { self.as_mut().map(T::conceal_all).unwrap_or_default() }
+}
+
+impl<T: ConcealAll> ConcealAll for [T] {
+    fn conceal_all(&mut self) -> usize { self.iter_mut().map(T::conceal_all).sum() }
+}
+
+impl<T: ConcealAll, const LEN: usize> ConcealAll for [T; LEN] {
+    fn conceal_all(&mut self) -> usize { self.as_mut_slice().conceal_all() }
+}
+
+impl<T: ConcealAll> ConcealAll for Vec<T> {
+    fn conceal_all(&mut self) -> usize { self.as_mut_slice().conceal_all() }
+}
+
+impl<K, V: ConcealAll> ConcealAll for BTreeMap<K, V> {
+    fn conceal_all(&mut self) -> usize { self.values_mut().map(V::conceal_all).sum() }
+}
+
+impl<T: ConcealAll, const MIN: usize, const MAX: usize> ConcealAll for Confined<Vec<T>, MIN, MAX> {
+    fn conceal_all(&mut self) -> usize { self.iter_mut().map(T::conceal_all).sum() }
+}
+
+impl<K: Ord + Hash, V: ConcealAll, const MIN: usize, const MAX: usize> ConcealAll
+    for Confined<BTreeMap<K, V>, MIN, MAX>
+{
+    fn conceal_all(&mut self) -> usize { self.values_mut().map(V::conceal_all).sum() }
+}
+
+#[cfg(test)]
+mod test {
+    use amplify::confinement::{SmallOrdMap, SmallVec};
+
+    use super::*;
+
+    #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+    enum Secret {
+        Revealed(u32),
+        Concealed(u32),
+    }
+
+    impl Conceal for Secret {
+        type Concealed = Secret;
+        fn conceal(&self) -> Secret {
+            match *self {
+                Secret::Revealed(val) => Secret::Concealed(!val),
+                concealed => concealed,
+            }
+        }
+    }
+
+    impl ConcealAll for Secret {
+        fn conceal_all(&mut self) -> usize {
+            let revealed = matches!(self, Secret::Revealed(_));
+            *self = self.conceal();
+            revealed as usize
+        }
+    }
+
+    #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+    #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+    #[strict_type(lib = crate::LIB_NAME_COMMIT_VERIFY)]
+    struct ConcealHashed(u64);
+
+    impl CommitEncode for ConcealHashed {
+        type CommitmentId = StrictHash;
+        fn commit_encode(&self, e: &mut crate::CommitEngine) { e.commit_to_serialized(self) }
+    }
+
+    #[derive(Clone, Eq, PartialEq, Debug)]
+    struct Transition {
+        meta: Option<Secret>,
+        inputs: SmallVec<Secret>,
+        outputs: SmallOrdMap<u16, [Secret; 2]>,
+    }
+
+    impl ConcealAll for Transition {
+        fn conceal_all(&mut self) -> usize {
+            self.meta.conceal_all() + self.inputs.conceal_all() + self.outputs.conceal_all()
+        }
+    }
+
+    #[test]
+    fn conceal_by_hash() {
+        assert_eq!(ConcealByHash::conceal(&()), ().commit_id());
+        assert_eq!(ConcealByHash::conceal(&ConcealHashed(1)), ConcealHashed(1).commit_id());
+        assert_ne!(
+            ConcealByHash::conceal(&ConcealHashed(1)),
+            ConcealByHash::conceal(&ConcealHashed(2))
+        );
+    }
+
+    #[test]
+    fn conceal_all() {
+        let mut transition = Transition {
+            meta: Some(Secret::Revealed(1)),
+            inputs: small_vec![Secret::Revealed(2), Secret::Concealed(3)],
+            outputs: small_bmap! {
+                0 => [Secret::Revealed(4), Secret::Revealed(5)],
+                1 => [Secret::Concealed(6), Secret::Revealed(7)],
+            },
+        };
+
+        let mut manual = transition.clone();
+        manual.meta = manual.meta.map(|s| s.conceal());
+        for input in manual.inputs.iter_mut() {
+            *input = input.conceal();
+        }
+        for output in manual.outputs.values_mut() {
+            *output = output.map(|s| s.conceal());
+        }
+
+        assert_eq!(transition.conceal_all(), 5);
+        assert_eq!(transition, manual);
+        assert_eq!(transition.conceal_all(), 0);
+        assert_eq!(transition, manual);
+    }
+}
@@ -29,14 +29,25 @@ pub trait DigestExt<const BYTE_LEN: usize = 32>: Digest {
     fn input_with_len<const MAX: usize>(&mut self, data: &[u8]) {
         let len = data.len();
         match MAX {
-            0..=0xFF => self.input_raw(&(len as u8).to_le_bytes()),
-            0x100..=0xFFFF => self.input_raw(&(len as u16).to_le_bytes()),
-            0x10000..=0xFFFFFF => self.input_raw(&u24::with(len as u32).to_le_bytes()),
-            0x1000000..=0xFFFFFFFF => self.input_raw(&(len as u32).to_le_bytes()),
+            0..=0xFF => self.input_u8(len as u8),
+            0x100..=0xFFFF => self.input_u16_le(len as u16),
+            0x10000..=0xFFFFFF => self.input_u24_le(u24::with(len as u32)),
+            0x1000000..=0xFFFFFFFF => self.input_u32_le(len as u32),
             _ => panic!("data too large"),
         }
         self.input_raw(data);
     }
+    fn input_u8(&mut self, val: u8) { self.input_raw(&[val]); }
+    fn input_u16_le(&mut self, val: u16) { self.input_raw(&val.to_le_bytes()); }
+    fn input_u16_be(&mut self, val: u16) { self.input_raw(&val.to_be_bytes()); }
+    fn input_u24_le(&mut self, val: u24) { self.input_raw(&val.to_le_bytes()); }
+    fn input_u24_be(&mut self, val: u24) { self.input_raw(&val.to_be_bytes()); }
+    fn input_u32_le(&mut self, val: u32) { self.input_raw(&val.to_le_bytes()); }
+    fn input_u32_be(&mut self, val: u32) { self.input_raw(&val.to_be_bytes()); }
+    fn input_u64_le(&mut self, val: u64) { self.input_raw(&val.to_le_bytes()); }
+    fn input_u64_be(&mut self, val: u64) { self.input_raw(&val.to_be_bytes()); }
+    fn input_u128_le(&mut self, val: u128) { self.input_raw(&val.to_le_bytes()); }
+    fn input_u128_be(&mut self, val: u128) { self.input_raw(&val.to_be_bytes()); }
     fn finish(self) -> [u8; BYTE_LEN];
 }
 
@@ -73,3 +84,30 @@ impl DigestExt<20> for Ripemd160 {
 
     fn finish(self) -> [u8; 20] { self.finalize().into() }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn input_int() {
+        fn check(f: impl Fn(&mut Sha256), bytes: &[u8]) {
+            let mut engine = Sha256::from_tag("test");
+            f(&mut engine);
+            let mut expected = Sha256::from_tag("test");
+            expected.input_raw(bytes);
+            assert_eq!(engine.finish(), expected.finish());
+        }
+
+        let x = 0xdeadbeef_u32;
+        check(|e| e.input_u32_le(x), &x.to_le_bytes());
+        check(|e| e.input_u32_be(x), &x.to_be_bytes());
+        check(|e| e.input_u8(0xA5), &[0xA5]);
+        check(|e| e.input_u16_le(0xcafe), &0xcafe_u16.to_le_bytes());
+        check(|e| e.input_u24_le(u24::with(0xbeef01)), &[0x01, 0xef, 0xbe]);
+        check(|e| e.input_u24_be(u24::with(0xbeef01)), &[0xbe, 0xef, 0x01]);
+        check(|e| e.input_u64_be(u64::MAX - 1), &(u64::MAX - 1).to_be_bytes());
+        check(|e| e.input_u128_le(1), &1u128.to_le_bytes());
+        check(|e| e.input_with_len::<0xFFFF>(&[1, 2]), &[2, 0, 1, 2]);
+    }
+}
@@ -79,6 +79,71 @@ where
     ) -> Result<Container, EmbedVerifyError<Container::CommitError>>;
 }
 
+/// Proofs produced by [`EmbedCommitVerify::embed_commit`] procedure which
+/// allow to verify the commitment by checking only the part of the container
+/// bearing the commitment (*commitment region*), without reconstructing the
+/// whole container.
+///
+/// For instance, in tapret-style commitments the region is the tweaked output
+/// key, which can be checked without restoring the rest of the transaction.
+///
+/// # Soundness
+///
+/// Region verification is equivalent to the full verification with
+/// [`EmbedCommitVerify::verify`] only when:
+/// - the region is the only part of the container which is affected by the
+///   commitment procedure, i.e. the rest of the container is kept intact;
+/// - the region (`container_commitment`) is taken from a container which is
+///   authenticated by other means (for instance, by its id or hash known to the
+///   verifier).
+///
+/// Otherwise, the verification of the region doesn't prove that the
+/// container contains the commitment.
+pub trait EmbedCommitRegion<Msg, Container, Protocol>:
+    EmbedCommitProof<Msg, Container, Protocol>
+where
+    Container: EmbedCommitVerify<Msg, Protocol, Proof = Self>,
+    Protocol: CommitmentProtocol,
+{
+    /// Commitment-bearing part of the container.
+    type Region: VerifyEq;
+
+    /// Computes the commitment region which must be present in the container
+    /// committing to the `msg` with this proof.
+    ///
+    /// # Error
+    ///
+    /// If the commitment to the message can't be created returns
+    /// [`EmbedVerifyError::InvalidMessage`]; if the proof data are invalid
+    /// returns [`EmbedVerifyError::InvalidProof`].
+    fn commit_region(
+        &self,
+        msg: &Msg,
+    ) -> Result<Self::Region, EmbedVerifyError<Container::CommitError>>;
+
+    /// Verifies the commitment to the `msg` using the proof (the `self`)
+    /// against the commitment region of the container (`container_commitment`)
+    /// without reconstructing the whole container. Please check
+    /// [soundness](EmbedCommitRegion#soundness) conditions before using the
+    /// method.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the commitment doesn't pass the validation (see
+    /// [`EmbedVerifyError`] variants for the cases when this may happen).
+    fn verify_region(
+        &self,
+        msg: &Msg,
+        container_commitment: &Self::Region,
+    ) -> Result<(), EmbedVerifyError<Container::CommitError>> {
+        let region = self.commit_region(msg)?;
+        if !region.verify_eq(container_commitment) {
+            return Err(EmbedVerifyError::CommitmentMismatch);
+        }
+        Ok(())
+    }
+}
+
 /// Trait for *embed-commit-verify scheme*, where some data structure (named
 /// *container*) may commit to existing *message* (producing *commitment* data
 /// structure and a *proof*) in such way that the original message can't be
@@ -372,6 +437,91 @@ mod test {
         fn extract_supplement(&self) -> &Self::Suppl { self }
     }
 
+    /// Container modelling tapret-style commitment, where the commitment is a
+    /// tweak of the output key, while the rest of the data are not affected.
+    #[derive(Clone, PartialEq, Eq, Debug, Hash)]
+    struct DummyTx {
+        inputs: SmallBlob,
+        output_key: [u8; 32],
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Hash)]
+    struct InternalKey([u8; 32]);
+
+    impl InternalKey {
+        fn tweak(&self, msg: &[u8]) -> [u8; 32] {
+            let mut engine = Sha256::default();
+            engine.input_raw(&self.0);
+            engine.input_with_len::<U32>(msg);
+            engine.finish()
+        }
+    }
+
+    impl<T> EmbedCommitProof<T, DummyTx, TestProtocol> for InternalKey
+    where T: AsRef<[u8]> + Clone
+    {
+        fn restore_original_container(
+            &self,
+            tx: &DummyTx,
+        ) -> Result<DummyTx, EmbedVerifyError<Error>> {
+            Ok(DummyTx {
+                inputs: tx.inputs.clone(),
+                output_key: self.0,
+            })
+        }
+    }
+
+    impl<T> EmbedCommitRegion<T, DummyTx, TestProtocol> for InternalKey
+    where T: AsRef<[u8]> + Clone
+    {
+        type Region = [u8; 32];
+
+        fn commit_region(&self, msg: &T) -> Result<Self::Region, EmbedVerifyError<Error>> {
+            Ok(self.tweak(msg.as_ref()))
+        }
+    }
+
+    impl<T> EmbedCommitVerify<T, TestProtocol> for DummyTx
+    where T: AsRef<[u8]> + Clone
+    {
+        type Proof = InternalKey;
+        type CommitError = Error;
+
+        fn embed_commit(&mut self, msg: &T) -> Result<Self::Proof, Self::CommitError> {
+            let proof = InternalKey(self.output_key);
+            self.output_key = proof.tweak(msg.as_ref());
+            Ok(proof)
+        }
+    }
+
+    #[test]
+    fn test_verify_region() {
+        let messages: Vec<SmallVec<u8>> = gen_messages();
+        let tx = DummyTx {
+            inputs: small_vec![0xA5; 1024],
+            output_key: [0xC0; 32],
+        };
+        embed_commit_verify_suite::<SmallVec<u8>, DummyTx>(messages.clone(), tx.clone());
+
+        for msg in &messages {
+            let mut commitment = tx.clone();
+            let proof = commitment.embed_commit(msg).unwrap();
+            assert_eq!(commitment.verify(msg, &proof), Ok(()));
+            assert_eq!(proof.verify_region(msg, &commitment.output_key), Ok(()));
+
+            let mut other = msg.clone();
+            other.push(0xFF).unwrap();
+            assert_eq!(
+                commitment.verify(&other, &proof),
+                Err(EmbedVerifyError::CommitmentMismatch)
+            );
+            assert_eq!(
+                proof.verify_region(&other, &commitment.output_key),
+                Err(EmbedVerifyError::CommitmentMismatch)
+            );
+        }
+    }
+
     #[test]
     fn test_embed_commit() {
         embed_commit_verify_suite::<SmallVec<u8>, DummyVec>(gen_messages(), DummyVec(default!()));
@@ -19,41 +19,100 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::cell::OnceCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::{self, Display, Formatter};
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::marker::PhantomData;
+use std::num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8};
+use std::ops::Deref;
+use std::rc::Rc;
 
-use amplify::confinement::{Confined, TinyVec, U64 as U64MAX};
+use amplify::confinement::{Confined, TinyVec, U32 as U32MAX, U64 as U64MAX};
 use amplify::Bytes32;
 use sha2::Sha256;
-use strict_encoding::{Sizing, StreamWriter, StrictDumb, StrictEncode, StrictType};
+use strict_encoding::{
+    DecodeError, DeserializeError, Sizing, StreamWriter, StrictDecode, StrictDeserialize,
+    StrictDumb, StrictEncode, StrictSerialize, StrictType, TypeName, TypedRead, TypedWrite,
+};
 use strict_types::typesys::TypeFqn;
+use strict_types::{SemId, TypeLib};
 
-use crate::{Conceal, DigestExt, MerkleHash, MerkleLeaves, LIB_NAME_COMMIT_VERIFY};
+use crate::{
+    mpc, Conceal, ContextualCommit, ContextualProtocol, DigestExt, MerkleHash, MerkleLeaves,
+    LIB_NAME_COMMIT_VERIFY,
+};
 
 const COMMIT_MAX_LEN: usize = U64MAX;
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_COMMIT_VERIFY, tags = order)]
 pub enum CommitColType {
+    #[strict_type(dumb)]
     List,
     Set,
-    Map { key: TypeFqn },
+    Map {
+        key: TypeFqn,
+    },
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_COMMIT_VERIFY, tags = order)]
 pub enum CommitStep {
+    Domain(TypeFqn),
     Serialized(TypeFqn),
     Collection(CommitColType, Sizing, TypeFqn),
     Hashed(TypeFqn),
     Merklized(TypeFqn),
     Concealed(TypeFqn),
+    DetachedSignature(TypeFqn),
+    Variant(TypeFqn),
+    #[strict_type(dumb)]
+    Raw,
+    Version(TypeFqn, u8),
+    NonZero(u8),
+    Schema(TypeFqn, SemId),
+}
+
+/// Snapshot of the state of an unfinished [`CommitEngine`], used for its
+/// persistence (see [`CommitEngine::to_bytes`]).
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+struct EngineSnapshot {
+    tag: Confined<Vec<u8>, 0, U32MAX>,
+    field_count_guard: bool,
+    layout: TinyVec<CommitStep>,
+    chunks: Confined<Vec<Confined<Vec<u8>, 0, U32MAX>>, 0, U32MAX>,
+    steps: Confined<Vec<u8>, 0, U32MAX>,
 }
 
+impl StrictSerialize for EngineSnapshot {}
+impl StrictDeserialize for EngineSnapshot {}
+
 #[derive(Clone, Debug)]
 pub struct CommitEngine {
     finished: bool,
+    field_count_guard: bool,
+    tag: &'static str,
     hasher: Sha256,
     layout: TinyVec<CommitStep>,
+    /// Chunks of the committed data, each with the number of the layout
+    /// steps at the moment the chunk was written (see
+    /// [`CommitTranscript::step_chunks`]).
+    transcript: Option<Vec<(u8, Vec<u8>)>>,
+}
+
+fn serialize<T: StrictEncode, const MAX_LEN: usize>(value: &T) -> Vec<u8> {
+    let mut data = vec![];
+    let ok = value
+        .strict_write(StreamWriter::new::<MAX_LEN>(&mut data))
+        .is_ok();
+    debug_assert!(ok);
+    data
 }
 
 fn commitment_fqn<T: StrictType>() -> TypeFqn {
@@ -67,8 +126,130 @@ impl CommitEngine {
     pub fn new(tag: &'static str) -> Self {
         Self {
             finished: false,
+            field_count_guard: false,
+            tag,
             hasher: Sha256::from_tag(tag),
             layout: empty!(),
+            transcript: None,
+        }
+    }
+
+    /// Constructs engine for commitments under runtime `context` (see
+    /// [`ContextualProtocol`]).
+    ///
+    /// The context is folded into the tag: the tagged hash prefix is computed
+    /// over the `tag` followed by a zero byte and the context data.
+    pub fn with_context(tag: &'static str, context: &[u8]) -> Self {
+        let mut engine = Self::new(tag);
+        engine.hasher = Sha256::from_tag([tag.as_bytes(), &[0], context].concat());
+        engine
+    }
+
+    /// Constructs engine which records all the data it commits to into a
+    /// [`CommitTranscript`] (see [`Self::into_transcript`]).
+    pub fn with_transcript(tag: &'static str) -> Self {
+        let mut engine = Self::new(tag);
+        engine.transcript = Some(empty!());
+        engine
+    }
+
+    /// Serializes the state of an unfinished engine, such that the commitment
+    /// can be resumed later with [`Self::from_bytes`].
+    ///
+    /// Since the hasher state can't be exported, the engine keeps all the data
+    /// committed so far, and only engines constructed with
+    /// [`Self::with_transcript`] can be persisted; for other engines the
+    /// function returns `None`.
+    ///
+    /// NB: The serialized engine is an internal format, which is not guaranteed
+    /// to be compatible across versions of the library.
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        debug_assert!(!self.finished, "finished engine can't be persisted");
+        let transcript = self.transcript.as_ref()?;
+        let chunks = transcript
+            .iter()
+            .map(|(_, chunk)| Confined::try_from(chunk.clone()).expect("too large committed data"))
+            .collect::<Vec<_>>();
+        let steps = transcript.iter().map(|(step, _)| *step).collect::<Vec<_>>();
+        let snapshot = EngineSnapshot {
+            tag: Confined::try_from(self.tag.as_bytes().to_vec()).expect("too large tag"),
+            field_count_guard: self.field_count_guard,
+            layout: self.layout.clone(),
+            chunks: Confined::try_from(chunks).expect("too many committed chunks"),
+            steps: Confined::try_from(steps).expect("too many committed chunks"),
+        };
+        let data = snapshot
+            .to_strict_serialized::<U64MAX>()
+            .expect("in-memory serialization");
+        Some(data.release())
+    }
+
+    /// Resumes the engine for the commitment with the given `tag` from the
+    /// state serialized with [`Self::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// If the data are not a valid engine state, or the engine was used for
+    /// the commitment with a different tag.
+    pub fn from_bytes(tag: &'static str, data: &[u8]) -> Result<Self, DeserializeError> {
+        let data = Confined::try_from(data.to_vec())
+            .map_err(|e| DecodeError::DataIntegrityError(e.to_string()))?;
+        let snapshot = EngineSnapshot::from_strict_serialized::<U64MAX>(data)?;
+        if snapshot.tag.as_slice() != tag.as_bytes() {
+            return Err(DecodeError::DataIntegrityError(format!(
+                "engine state is for a commitment with a tag other than `{tag}`"
+            ))
+            .into());
+        }
+        if snapshot.steps.len() != snapshot.chunks.len() ||
+            snapshot
+                .steps
+                .iter()
+                .any(|step| *step as usize > snapshot.layout.len())
+        {
+            return Err(DecodeError::DataIntegrityError(s!(
+                "engine state has inconsistent layout of the committed data"
+            ))
+            .into());
+        }
+        let mut engine = Self::with_transcript(tag);
+        engine.field_count_guard = snapshot.field_count_guard;
+        engine.layout = snapshot.layout;
+        let chunks = engine.transcript.as_mut().expect("transcript engine");
+        for (step, chunk) in snapshot.steps.into_iter().zip(snapshot.chunks) {
+            engine.hasher.input_raw(&chunk);
+            chunks.push((step, chunk.release()));
+        }
+        Ok(engine)
+    }
+
+    /// Constructs engine which folds the final number of commitment steps into
+    /// the commitment (see [`Self::set_field_count_guard`]).
+    pub fn with_field_count_guard(tag: &'static str) -> Self {
+        let mut engine = Self::new(tag);
+        engine.set_field_count_guard();
+        engine
+    }
+
+    /// Enables field count guard, which makes the engine to commit to the
+    /// total number of commitment steps once the engine is finished. This
+    /// prevents collisions between types where the data of one of them are a
+    /// prefix of the data of the other one.
+    ///
+    /// NB: The guard changes the commitment value, thus it must be enabled per
+    /// type and must not be enabled for types which already have their
+    /// commitments in use.
+    pub fn set_field_count_guard(&mut self) {
+        debug_assert!(!self.finished);
+        self.field_count_guard = true;
+    }
+
+    /// Writes data which are already strict-serialized.
+    fn inner_commit_serialized(&mut self, chunk: Vec<u8>) {
+        debug_assert!(!self.finished);
+        self.hasher.input_raw(&chunk);
+        if let Some(transcript) = &mut self.transcript {
+            transcript.push((self.layout.len_u8(), chunk));
         }
     }
 
@@ -77,6 +258,102 @@ impl CommitEngine {
         let writer = StreamWriter::new::<MAX_LEN>(&mut self.hasher);
         let ok = value.strict_write(writer).is_ok();
         debug_assert!(ok);
+        if let Some(transcript) = &mut self.transcript {
+            let mut chunk = vec![];
+            let ok = value
+                .strict_write(StreamWriter::new::<MAX_LEN>(&mut chunk))
+                .is_ok();
+            debug_assert!(ok);
+            transcript.push((self.layout.len_u8(), chunk));
+        }
+    }
+
+    /// Commits to the domain of type `T`, such that two different types never
+    /// produce the same commitment, even if their data serialize into the same
+    /// bytes.
+    ///
+    /// The domain tag is the strict-serialized fully qualified type name of
+    /// `T`, i.e. its [`StrictType::STRICT_LIB_NAME`] followed by its strict
+    /// type name, each encoded as an ASCII string prefixed with a single byte
+    /// of its length. The tag is written to the hasher right after the
+    /// commitment id tag prefix.
+    ///
+    /// Must be called before committing to any data.
+    pub fn commit_to_domain<T: StrictType>(&mut self) {
+        debug_assert!(
+            self.layout.is_empty(),
+            "commitment domain must be provided before any other data"
+        );
+        let fqn = commitment_fqn::<T>();
+        self.layout
+            .push(CommitStep::Domain(fqn.clone()))
+            .expect("too many fields for commitment");
+
+        self.inner_commit_to::<_, COMMIT_MAX_LEN>(&fqn);
+    }
+
+    /// Commits to the versioned domain of type `T`, such that different
+    /// versions of the same type (see [`CommitEncode::COMMITMENT_VERSION`])
+    /// never produce the same commitment, even if their data serialize into
+    /// the same bytes.
+    ///
+    /// The discriminant is the same as the domain tag of
+    /// [`Self::commit_to_domain`], followed by a single byte of the version.
+    ///
+    /// Must be called before committing to any data.
+    pub fn commit_to_version<T: CommitEncode + StrictType>(&mut self) {
+        debug_assert!(
+            self.layout.is_empty(),
+            "commitment version must be provided before any other data"
+        );
+        let fqn = commitment_fqn::<T>();
+        self.layout
+            .push(CommitStep::Version(fqn.clone(), T::COMMITMENT_VERSION))
+            .expect("too many fields for commitment");
+
+        self.inner_commit_to::<_, COMMIT_MAX_LEN>(&fqn);
+        self.inner_commit_to::<_, 1>(&T::COMMITMENT_VERSION);
+    }
+
+    /// Commits to the strict type schema of type `T`, such that types coming
+    /// from different schemas never produce the same commitment, even if they
+    /// are structurally identical and their data serialize into the same
+    /// bytes.
+    ///
+    /// The discriminant is the same as the domain tag of
+    /// [`Self::commit_to_domain`], followed by the 32 bytes of the semantic
+    /// type id `sem_id` of `T`, which commits to its name, field and variant
+    /// names and memory layout. The id must be taken from a strict type
+    /// library registering `T`, for instance with [`type_sem_id`].
+    ///
+    /// Must be called before committing to any data.
+    pub fn commit_to_schema<T: StrictType>(&mut self, sem_id: SemId) {
+        debug_assert!(
+            self.layout.is_empty(),
+            "commitment schema must be provided before any other data"
+        );
+        let fqn = commitment_fqn::<T>();
+        self.layout
+            .push(CommitStep::Schema(fqn.clone(), sem_id))
+            .expect("too many fields for commitment");
+
+        self.inner_commit_to::<_, COMMIT_MAX_LEN>(&fqn);
+        self.inner_commit_to::<_, 32>(&sem_id);
+    }
+
+    /// Commits to the strict type schema of type `T` using its semantic type
+    /// id from the strict type library registering the type, provided by
+    /// [`CommitmentSchema::commitment_lib`] (see [`Self::commit_to_schema`]).
+    ///
+    /// Must be called before committing to any data.
+    ///
+    /// # Panics
+    ///
+    /// If the library doesn't register `T` (see [`type_sem_id`]).
+    pub fn commit_to_lib_schema<T: CommitmentSchema>(&mut self) {
+        let sem_id = type_sem_id::<T>(T::commitment_lib())
+            .expect("strict type library doesn't register the committed type");
+        self.commit_to_schema::<T>(sem_id);
     }
 
     pub fn commit_to_serialized<T: StrictEncode>(&mut self, value: &T) {
@@ -99,6 +376,31 @@ impl CommitEngine {
         self.inner_commit_to::<_, COMMIT_MAX_LEN>(&value);
     }
 
+    /// Starts commitment to the strict-serialized fields of structure `T`,
+    /// which are provided one by one with [`Self::commit_to_field`].
+    ///
+    /// Unlike [`Self::commit_to_serialized`], allows to omit some of the
+    /// fields (like detached signatures, see
+    /// [`Self::commit_to_detached_signature`]) from the commitment without
+    /// cloning the structure. If all the fields are provided in their order,
+    /// the commitment is the same as with [`Self::commit_to_serialized`].
+    pub fn commit_to_fields<T: StrictType>(&mut self) {
+        let fqn = commitment_fqn::<T>();
+        self.layout
+            .push(CommitStep::Serialized(fqn))
+            .expect("too many fields for commitment");
+    }
+
+    /// Commits to the strict-serialized `value` of a structure field, as a part
+    /// of the commitment started with [`Self::commit_to_fields`].
+    pub fn commit_to_field<F: StrictEncode>(&mut self, value: &F) {
+        debug_assert!(
+            matches!(self.layout.last(), Some(CommitStep::Serialized(_))),
+            "structure fields must be committed right after commit_to_fields"
+        );
+        self.inner_commit_to::<_, COMMIT_MAX_LEN>(value);
+    }
+
     pub fn commit_to_option<T: StrictEncode + StrictDumb>(&mut self, value: &Option<T>) {
         let fqn = commitment_fqn::<T>();
         self.layout
@@ -108,6 +410,18 @@ impl CommitEngine {
         self.inner_commit_to::<_, COMMIT_MAX_LEN>(&value);
     }
 
+    /// Commits to an optional non-zero integer using its niche: `None` is
+    /// encoded as integer zero and `Some(n)` as `n`, without the option tag
+    /// byte used by [`Self::commit_to_option`].
+    pub fn commit_to_nonzero<T: NonZeroInt>(&mut self, value: Option<T>) {
+        self.layout
+            .push(CommitStep::NonZero(T::BITS))
+            .expect("too many fields for commitment");
+
+        let int = value.map(T::get_int).unwrap_or(T::ZERO);
+        self.inner_commit_to::<_, COMMIT_MAX_LEN>(&int);
+    }
+
     pub fn commit_to_hash<T: CommitEncode<CommitmentId = StrictHash> + StrictType>(
         &mut self,
         value: &T,
@@ -145,6 +459,51 @@ impl CommitEngine {
         self.inner_commit_to::<_, COMMIT_MAX_LEN>(&concealed);
     }
 
+    /// Commits to the variant `tag` of enum type `T`.
+    pub fn commit_to_variant<T: StrictType>(&mut self, tag: u8) {
+        let fqn = commitment_fqn::<T>();
+        self.layout
+            .push(CommitStep::Variant(fqn))
+            .expect("too many fields for commitment");
+
+        self.inner_commit_to::<_, 1>(&tag);
+    }
+
+    /// Commits to an opaque pre-serialized byte region verbatim.
+    ///
+    /// Unlike [`Self::commit_to_serialized`], the data are not a typed value
+    /// and are not re-serialized. To prevent extension attacks, the bytes are
+    /// delimited with a prefix of their length, encoded as a 32-bit
+    /// little-endian integer, such that successive raw regions can't be split
+    /// differently while producing the same commitment.
+    ///
+    /// # Panics
+    ///
+    /// If the length of the data exceeds `u32::MAX`.
+    pub fn commit_to_raw(&mut self, bytes: &[u8]) {
+        debug_assert!(!self.finished);
+        let len = u32::try_from(bytes.len()).expect("raw data length exceeds 2^32 bytes");
+        self.layout
+            .push(CommitStep::Raw)
+            .expect("too many fields for commitment");
+
+        let chunk = [&len.to_le_bytes()[..], bytes].concat();
+        self.hasher.input_raw(&chunk);
+        if let Some(transcript) = &mut self.transcript {
+            transcript.push((self.layout.len_u8(), chunk));
+        }
+    }
+
+    /// Records presence of a detached signature of type `T` in the commitment
+    /// layout. The signature itself is not committed to.
+    pub fn commit_to_detached_signature<T: StrictType>(&mut self) {
+        debug_assert!(!self.finished);
+        let fqn = commitment_fqn::<T>();
+        self.layout
+            .push(CommitStep::DetachedSignature(fqn))
+            .expect("too many fields for commitment");
+    }
+
     pub fn commit_to_linear_list<T, const MIN: usize, const MAX: usize>(
         &mut self,
         collection: &Confined<Vec<T>, MIN, MAX>,
@@ -195,24 +554,381 @@ impl CommitEngine {
         self.inner_commit_to::<_, COMMIT_MAX_LEN>(&collection);
     }
 
+    /// Commits to an unordered hash set, making the commitment deterministic
+    /// by ordering the elements by their commitment ids. Elements with the
+    /// same commitment id are ordered by their strict-serialized data.
+    ///
+    /// The set is committed as its length, encoded as a 32-bit little-endian
+    /// integer, followed by the strict-serialized elements in that order.
+    ///
+    /// # Panics
+    ///
+    /// If the set contains more than `u32::MAX` elements.
+    pub fn commit_to_hash_set<T, S>(&mut self, collection: &HashSet<T, S>)
+    where T: CommitEncode + StrictEncode + StrictType {
+        let fqn = commitment_fqn::<T>();
+        let step = CommitStep::Collection(CommitColType::Set, Sizing::new(0, u32::MAX as u64), fqn);
+        self.layout
+            .push(step)
+            .expect("too many fields for commitment");
+
+        let mut elements = collection
+            .iter()
+            .map(|item| (item.commit_id(), serialize::<_, COMMIT_MAX_LEN>(item)))
+            .collect::<Vec<_>>();
+        elements.sort();
+        let len = u32::try_from(elements.len()).expect("too many elements for commitment");
+        self.inner_commit_to::<_, 4>(&len);
+        for (_, item) in elements {
+            self.inner_commit_serialized(item);
+        }
+    }
+
+    /// Commits to an unordered hash map, making the commitment deterministic
+    /// by ordering the entries by the commitment ids of their keys. Entries
+    /// with the same commitment id of the keys are ordered by the
+    /// strict-serialized data of their keys and then of their values.
+    ///
+    /// The map is committed as its length, encoded as a 32-bit little-endian
+    /// integer, followed by the strict-serialized keys, each followed by its
+    /// value.
+    ///
+    /// # Panics
+    ///
+    /// If the map contains more than `u32::MAX` entries.
+    pub fn commit_to_hash_map<K, V, S>(&mut self, collection: &HashMap<K, V, S>)
+    where
+        K: CommitEncode + StrictEncode + StrictType,
+        V: StrictEncode + StrictType,
+    {
+        let key_fqn = commitment_fqn::<K>();
+        let val_fqn = commitment_fqn::<V>();
+        let step = CommitStep::Collection(
+            CommitColType::Map { key: key_fqn },
+            Sizing::new(0, u32::MAX as u64),
+            val_fqn,
+        );
+        self.layout
+            .push(step)
+            .expect("too many fields for commitment");
+
+        let mut entries = collection
+            .iter()
+            .map(|(key, val)| {
+                let key_data = serialize::<_, COMMIT_MAX_LEN>(key);
+                (key.commit_id(), key_data, serialize::<_, COMMIT_MAX_LEN>(val))
+            })
+            .collect::<Vec<_>>();
+        entries.sort();
+        let len = u32::try_from(entries.len()).expect("too many entries for commitment");
+        self.inner_commit_to::<_, 4>(&len);
+        for (_, key, val) in entries {
+            self.inner_commit_serialized(key);
+            self.inner_commit_serialized(val);
+        }
+    }
+
     pub fn as_layout(&mut self) -> &[CommitStep] {
-        self.finished = true;
+        self.set_finished();
         self.layout.as_ref()
     }
 
     pub fn into_layout(self) -> TinyVec<CommitStep> { self.layout }
 
-    pub fn set_finished(&mut self) { self.finished = true; }
+    pub fn set_finished(&mut self) {
+        if !self.finished && self.field_count_guard {
+            let count = self.layout.len() as u8;
+            self.hasher.input_u8(count);
+            if let Some(transcript) = &mut self.transcript {
+                // The count doesn't belong to any of the layout steps
+                transcript.push((0, vec![count]));
+            }
+        }
+        self.finished = true;
+    }
+
+    pub fn finish(mut self) -> Sha256 {
+        self.set_finished();
+        self.hasher
+    }
+
+    pub fn finish_layout(mut self) -> (Sha256, TinyVec<CommitStep>) {
+        self.set_finished();
+        (self.hasher, self.layout)
+    }
+
+    /// Finishes the engine and returns the transcript of the commitment, if the
+    /// engine was constructed with [`Self::with_transcript`].
+    pub fn into_transcript(mut self) -> Option<CommitTranscript> {
+        self.set_finished();
+        let (steps, chunks) = self.transcript?.into_iter().unzip();
+        Some(CommitTranscript {
+            tag: self.tag,
+            layout: self.layout,
+            chunks,
+            steps,
+        })
+    }
+}
+
+/// Type registered in a strict type library, which may commit to its strict
+/// type schema with [`CommitEngine::commit_to_lib_schema`].
+pub trait CommitmentSchema: StrictType {
+    /// Returns the strict type library registering the type.
+    fn commitment_lib() -> &'static TypeLib;
+}
+
+/// Returns semantic id of type `T` from the strict type library `lib`, or
+/// `None` if the library doesn't contain the type or the library name doesn't
+/// match [`StrictType::STRICT_LIB_NAME`] of the type.
+///
+/// Used to obtain the id for [`CommitEngine::commit_to_schema`] and
+/// [`CommitEngine::commit_to_lib_schema`].
+pub fn type_sem_id<T: StrictType>(lib: &TypeLib) -> Option<SemId> {
+    let name = T::strict_name()?;
+    if lib.name.as_str() != T::STRICT_LIB_NAME {
+        return None;
+    }
+    lib.types.get(&name).map(|ty| ty.sem_id_named(&name))
+}
+
+/// Non-zero unsigned integer types, which may be committed to with
+/// [`CommitEngine::commit_to_nonzero`] using their niche.
+pub trait NonZeroInt: Copy {
+    /// Bit width of the integer.
+    const BITS: u8;
+    /// Zero value of the underlying integer type, representing `None`.
+    const ZERO: Self::Int;
+    /// Underlying integer type.
+    type Int: StrictEncode;
+
+    /// Returns the value as the underlying integer type.
+    fn get_int(self) -> Self::Int;
+}
 
-    pub fn finish(self) -> Sha256 { self.hasher }
+macro_rules! impl_nonzero_int {
+    ($($ty:ty => $int:ty),+ $(,)?) => {
+        $(
+            impl NonZeroInt for $ty {
+                const BITS: u8 = <$int>::BITS as u8;
+                const ZERO: $int = 0;
+                type Int = $int;
 
-    pub fn finish_layout(self) -> (Sha256, TinyVec<CommitStep>) { (self.hasher, self.layout) }
+                fn get_int(self) -> $int { self.get() }
+            }
+        )+
+    };
+}
+
+impl_nonzero_int!(NonZeroU8 => u8, NonZeroU16 => u16, NonZeroU32 => u32, NonZeroU64 => u64);
+
+/// Transcript of the commitment procedure, consisting of the commitment layout
+/// and the ordered list of the serialized data chunks fed into the hasher.
+///
+/// The transcript allows to check that the commitment id was computed over the
+/// layout of a given type without access to the original data structure. Since
+/// the chunks contain the committed (i.e. already concealed, merklized or
+/// hashed) data, the transcript reveals them.
+#[derive(Getters, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct CommitTranscript {
+    #[getter(as_copy)]
+    tag: &'static str,
+    layout: TinyVec<CommitStep>,
+    chunks: Vec<Vec<u8>>,
+    /// Number of the layout steps at the moment each of the chunks was
+    /// written, i.e. the index of the step producing the chunk plus one, or
+    /// zero for the chunks not belonging to any step.
+    #[getter(skip)]
+    steps: Vec<u8>,
+}
+
+impl CommitTranscript {
+    /// Returns chunks written by the layout step with the given `index`, as
+    /// reported by the producer of the transcript.
+    pub fn step_chunks(&self, index: usize) -> impl Iterator<Item = &[u8]> {
+        self.steps
+            .iter()
+            .zip(&self.chunks)
+            .filter(move |(step, _)| **step as usize == index + 1)
+            .map(|(_, chunk)| chunk.as_slice())
+    }
+
+    /// Returns offset of the data of the layout step with the given `index` in
+    /// the committed `data`, derived from the sizes of the preceding steps, or
+    /// `None` if any of them has a variable size.
+    fn step_offset(&self, data: &[u8], index: usize) -> Option<usize> {
+        let mut offset = 0usize;
+        for step in self.layout.get(..index)? {
+            offset += match step {
+                CommitStep::Domain(fqn) => serialize::<_, COMMIT_MAX_LEN>(fqn).len(),
+                CommitStep::Version(fqn, _) => serialize::<_, COMMIT_MAX_LEN>(fqn).len() + 1,
+                CommitStep::Schema(fqn, _) => serialize::<_, COMMIT_MAX_LEN>(fqn).len() + 32,
+                CommitStep::Hashed(_) | CommitStep::Merklized(_) => 32,
+                CommitStep::Variant(_) => 1,
+                CommitStep::NonZero(bits) => *bits as usize / 8,
+                CommitStep::DetachedSignature(_) => 0,
+                CommitStep::Raw => {
+                    let len = data.get(offset..offset + 4)?;
+                    4 + u32::from_le_bytes(len.try_into().ok()?) as usize
+                }
+                CommitStep::Serialized(_) |
+                CommitStep::Concealed(_) |
+                CommitStep::Collection(..) => return None,
+            };
+        }
+        Some(offset)
+    }
+
+    /// Verifies that the transcript follows the commitment layout of type `T`
+    /// and that replaying its data produces the provided commitment `id`.
+    ///
+    /// The layout of `T` is the one of its [`StrictDumb`] value (see
+    /// [`CommitmentLayout`]), thus the transcripts of types whose layout
+    /// depends on the value do not verify.
+    ///
+    /// NB: The boundaries of the chunks are not a part of the commitment, thus
+    /// they are not authenticated, and the distribution of the data between
+    /// the layout steps reported by [`Self::step_chunks`] can't be trusted.
+    pub fn verify_against<T: CommitmentLayout>(&self, id: T::CommitmentId) -> bool {
+        if self.tag != T::CommitmentId::TAG || self.layout != T::commitment_layout().fields {
+            return false;
+        }
+        let mut hasher = Sha256::from_tag(self.tag);
+        for chunk in &self.chunks {
+            hasher.input_raw(chunk);
+        }
+        T::CommitmentId::from(hasher) == id
+    }
+}
+
+/// Optional value with a commitment which always distinguishes an absent
+/// value from any present one.
+///
+/// A commitment-safe alternative to [`Option`] for the values where absence
+/// has a meaning different from a zero or an empty value (like a niche) and
+/// the commit-encoding of the value itself may not be self-delimiting:
+/// [`Presence::Absent`] and [`Presence::Present`] are committed as a single raw
+/// region (see [`CommitEngine::commit_to_raw`]) with a distinct tag, which for
+/// a present value is followed by its commitment id. Since the id is computed
+/// separately, the value may commit to its own domain, version or schema.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum Presence<T> {
+    /// No value is set.
+    #[default]
+    Absent,
+    /// Value is set.
+    Present(T),
+}
+
+impl<T> From<Option<T>> for Presence<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            None => Presence::Absent,
+            Some(value) => Presence::Present(value),
+        }
+    }
+}
+
+impl<T> From<Presence<T>> for Option<T> {
+    fn from(value: Presence<T>) -> Self {
+        match value {
+            Presence::Absent => None,
+            Presence::Present(value) => Some(value),
+        }
+    }
+}
+
+impl<T: CommitEncode> CommitEncode for Presence<T>
+where T::CommitmentId: StrictEncode
+{
+    type CommitmentId = T::CommitmentId;
+
+    fn commit_encode(&self, e: &mut CommitEngine) {
+        match self {
+            Presence::Absent => e.commit_to_raw(&[0x00]),
+            Presence::Present(value) => {
+                let id = serialize::<_, COMMIT_MAX_LEN>(&value.commit_id());
+                e.commit_to_raw(&[&[0x01][..], &id].concat());
+            }
+        }
+    }
+}
+
+/// Bundle allowing selective disclosure of the fields of a commit-encoded
+/// value, verifiable against the commitment id of the whole value.
+///
+/// The bundle is built from the [`CommitTranscript`] of the value. Fields
+/// committed with [`CommitEngine::commit_to_hash`] are present in the
+/// transcript only as their commitment ids, thus they stay hidden unless they
+/// are revealed with [`RevealBundle::verify_reveal`]. Fields committed in any
+/// other way (for instance, with [`CommitEngine::commit_to_serialized`]) are
+/// disclosed by the bundle itself.
+#[derive(Getters, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct RevealBundle<T: CommitEncode> {
+    transcript: CommitTranscript,
+    #[getter(skip)]
+    _phantom: PhantomData<T>,
+}
+
+impl<T: CommitEncode> RevealBundle<T> {
+    /// Constructs bundle for the given value.
+    pub fn new(value: &T) -> Self {
+        Self {
+            transcript: value.commit_transcript(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: CommitmentLayout> RevealBundle<T> {
+    /// Verifies that the bundle corresponds to the commitment `id`.
+    pub fn verify(&self, id: T::CommitmentId) -> bool { self.transcript.verify_against::<T>(id) }
+
+    /// Verifies that the revealed `field` is a hashed field of a value with
+    /// the commitment `id`.
+    ///
+    /// The field is matched against the data committed by the layout step
+    /// hashing a value of type `F`; if the layout contains several such steps,
+    /// the reveal is ambiguous and the verification fails.
+    ///
+    /// The position of the step data is derived from the layout of `T` and
+    /// not from the chunks of the transcript, thus the step must be preceded
+    /// only by the steps of a fixed size (i.e. domain, version, schema,
+    /// variant, non-zero, raw, hashed and merklized steps); otherwise the
+    /// reveal can't be verified and the verification fails.
+    pub fn verify_reveal<F>(&self, id: T::CommitmentId, field: &F) -> bool
+    where F: CommitEncode<CommitmentId = StrictHash> + StrictType {
+        if !self.verify(id) {
+            return false;
+        }
+        let step = CommitStep::Hashed(commitment_fqn::<F>());
+        let mut hashed = self
+            .transcript
+            .layout
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| **s == step)
+            .map(|(index, _)| index);
+        let (Some(index), None) = (hashed.next(), hashed.next()) else {
+            return false;
+        };
+        let data = self.transcript.chunks.concat();
+        let Some(offset) = self.transcript.step_offset(&data, index) else {
+            return false;
+        };
+        data.get(offset..offset + 32) == Some(field.commit_id().as_slice())
+    }
 }
 
 pub trait CommitEncode {
     /// Type of the resulting commitment.
     type CommitmentId: CommitmentId;
 
+    /// Version of the commitment scheme of the type. Used by
+    /// [`CommitEngine::commit_to_version`]; has no effect on commitments
+    /// which do not call that method.
+    const COMMITMENT_VERSION: u8 = 0;
+
     /// Encodes the data for the commitment by writing them directly into a
     /// [`std::io::Write`] writer instance
     fn commit_encode(&self, e: &mut CommitEngine);
@@ -273,6 +989,10 @@ pub trait CommitId: CommitEncode {
 
     /// Performs commitment to client-side-validated data
     fn commit_id(&self) -> Self::CommitmentId;
+
+    /// Performs commitment to client-side-validated data, producing the
+    /// transcript of the commitment procedure.
+    fn commit_transcript(&self) -> CommitTranscript;
 }
 
 impl<T: CommitEncode> CommitId for T {
@@ -284,6 +1004,194 @@ impl<T: CommitEncode> CommitId for T {
     }
 
     fn commit_id(&self) -> Self::CommitmentId { self.commit().finish().into() }
+
+    fn commit_transcript(&self) -> CommitTranscript {
+        let mut engine = CommitEngine::with_transcript(T::CommitmentId::TAG);
+        self.commit_encode(&mut engine);
+        engine
+            .into_transcript()
+            .expect("engine is constructed with transcript")
+    }
+}
+
+impl<T: CommitEncode, P: ContextualProtocol> ContextualCommit<T, P> for T::CommitmentId {
+    fn commit_in(protocol: &P, msg: &T) -> Self {
+        let mut engine = CommitEngine::with_context(T::CommitmentId::TAG, protocol.context());
+        msg.commit_encode(&mut engine);
+        engine.finish().into()
+    }
+}
+
+/// Wrapper around a commit-encodable value caching its commitment id.
+///
+/// The id is computed lazily on the first call to [`Committed::commit_id`].
+/// Mutable access to the value with [`Committed::get_mut`] resets the cached
+/// id; [`Committed::invalidate`] may be used to reset it manually.
+///
+/// The cache is not a part of the commitment, strict encoding or
+/// serialization, which are all performed over the wrapped value only.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(transparent, bound = "T: serde::Serialize + for<'d> serde::Deserialize<'d>")
+)]
+pub struct Committed<T: CommitEncode> {
+    value: T,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    id: OnceCell<T::CommitmentId>,
+}
+
+impl<T: CommitEncode> Committed<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            id: OnceCell::new(),
+        }
+    }
+
+    /// Returns commitment id of the wrapped value, computing it only if it
+    /// wasn't computed before or was invalidated since then.
+    pub fn commit_id(&self) -> T::CommitmentId { *self.id.get_or_init(|| self.value.commit_id()) }
+
+    /// Returns mutable access to the wrapped value, resetting the cached
+    /// commitment id.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.invalidate();
+        &mut self.value
+    }
+
+    /// Resets the cached commitment id, such that it will be re-computed on
+    /// the next call to [`Committed::commit_id`].
+    pub fn invalidate(&mut self) { self.id.take(); }
+
+    /// Detects whether the commitment id is computed and cached.
+    pub fn is_cached(&self) -> bool { self.id.get().is_some() }
+
+    pub fn into_inner(self) -> T { self.value }
+}
+
+impl<T: CommitEncode> From<T> for Committed<T> {
+    fn from(value: T) -> Self { Self::new(value) }
+}
+
+impl<T: CommitEncode> Deref for Committed<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target { &self.value }
+}
+
+impl<T: CommitEncode> AsRef<T> for Committed<T> {
+    fn as_ref(&self) -> &T { &self.value }
+}
+
+impl<T: CommitEncode + PartialEq> PartialEq for Committed<T> {
+    fn eq(&self, other: &Self) -> bool { self.value == other.value }
+}
+
+impl<T: CommitEncode + Eq> Eq for Committed<T> {}
+
+impl<T: CommitEncode + Hash> Hash for Committed<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) { self.value.hash(state) }
+}
+
+impl<T: CommitEncode> CommitEncode for Committed<T> {
+    type CommitmentId = T::CommitmentId;
+
+    fn commit_encode(&self, e: &mut CommitEngine) { self.value.commit_encode(e) }
+}
+
+impl<T: CommitEncode + StrictDumb> StrictDumb for Committed<T> {
+    fn strict_dumb() -> Self { Self::new(T::strict_dumb()) }
+}
+
+impl<T: CommitEncode + StrictType> StrictType for Committed<T> {
+    const STRICT_LIB_NAME: &'static str = T::STRICT_LIB_NAME;
+    fn strict_name() -> Option<TypeName> { T::strict_name() }
+}
+
+impl<T: CommitEncode + StrictEncode> StrictEncode for Committed<T> {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        self.value.strict_encode(writer)
+    }
+}
+
+impl<T: CommitEncode + StrictDecode> StrictDecode for Committed<T> {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        T::strict_decode(reader).map(Self::new)
+    }
+}
+
+impl<T: CommitEncode + StrictSerialize> StrictSerialize for Committed<T> {}
+impl<T: CommitEncode + StrictDeserialize> StrictDeserialize for Committed<T> {}
+
+/// Node of a directed acyclic graph of commit-encodable data, which commits to
+/// the nodes it references (its children) by their commitment ids.
+///
+/// Implementing the trait allows to compute commitment ids of the graph with
+/// [`CommitCache`], which computes the id of each of the nodes shared between
+/// multiple parents only once. The [`CommitEncode`] implementation of the node
+/// must be equivalent to calling [`DagCommit::commit_encode_with`] with the
+/// commitment ids of [`DagCommit::children`].
+pub trait DagCommit: CommitEncode + Sized {
+    /// Returns nodes directly referenced by this node, in the order in which
+    /// their commitment ids are passed to [`DagCommit::commit_encode_with`].
+    fn children(&self) -> impl Iterator<Item = &Rc<Self>>;
+
+    /// Commits to the node using the provided commitment ids of its
+    /// [`DagCommit::children`].
+    fn commit_encode_with(&self, e: &mut CommitEngine, children: &[Self::CommitmentId]);
+}
+
+/// Cache of the commitment ids of [`DagCommit`] nodes, keyed by the node
+/// identity (i.e. the address of the reference-counted node).
+///
+/// The cached nodes are kept alive by the cache, such that their addresses
+/// can't be reused by other nodes. Since the cache relies on the node
+/// identity, the nodes must not be mutated (for instance, via interior
+/// mutability) while the cache is in use.
+#[derive(Debug)]
+pub struct CommitCache<T: DagCommit> {
+    ids: HashMap<*const T, (Rc<T>, T::CommitmentId)>,
+}
+
+impl<T: DagCommit> Default for CommitCache<T> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<T: DagCommit> CommitCache<T> {
+    /// Constructs empty cache.
+    pub fn new() -> Self {
+        Self {
+            ids: HashMap::new(),
+        }
+    }
+
+    /// Returns number of the nodes with cached commitment ids.
+    pub fn len(&self) -> usize { self.ids.len() }
+
+    /// Detects whether the cache is empty.
+    pub fn is_empty(&self) -> bool { self.ids.is_empty() }
+
+    /// Returns commitment id of the `node`, reusing the cached ids of the
+    /// node and its descendants and caching all newly computed ids.
+    ///
+    /// The produced id is equal to the one returned by [`CommitId::commit_id`]
+    /// for the same node.
+    pub fn commit_id(&mut self, node: &Rc<T>) -> T::CommitmentId {
+        if let Some((_, id)) = self.ids.get(&Rc::as_ptr(node)) {
+            return *id;
+        }
+        let children = node
+            .children()
+            .map(|child| self.commit_id(child))
+            .collect::<Vec<_>>();
+        let mut engine = CommitEngine::new(T::CommitmentId::TAG);
+        node.commit_encode_with(&mut engine, &children);
+        engine.set_finished();
+        let id = engine.finish().into();
+        self.ids.insert(Rc::as_ptr(node), (node.clone(), id));
+        id
+    }
 }
 
 #[derive(Wrapper, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, From)]
@@ -304,3 +1212,597 @@ impl CommitmentId for StrictHash {
 impl From<Sha256> for StrictHash {
     fn from(hash: Sha256) -> Self { hash.finish().into() }
 }
+
+/// Commitment and hash types represented by 32 bytes, allowing uniform
+/// conversion to and from byte arrays in generic code.
+pub trait Commitment32: Copy + From<[u8; 32]> + Deref<Target = Bytes32> {
+    /// Returns the value as a byte array.
+    fn to_byte_array(&self) -> [u8; 32] { self.deref().to_byte_array() }
+
+    /// Constructs the value from a byte array.
+    fn from_byte_array(bytes: [u8; 32]) -> Self { Self::from(bytes) }
+}
+
+impl Commitment32 for StrictHash {}
+impl Commitment32 for MerkleHash {}
+impl Commitment32 for mpc::ProtocolId {}
+impl Commitment32 for mpc::Message {}
+impl Commitment32 for mpc::Commitment {}
+
+impl CommitEncode for () {
+    type CommitmentId = StrictHash;
+
+    fn commit_encode(&self, _: &mut CommitEngine) {}
+}
+
+/// Zero-sized type markers do not contribute any data to the commitment, thus
+/// they can be left as a part of commit-encoded types without special
+/// annotations.
+impl<T: ?Sized> CommitEncode for PhantomData<T> {
+    type CommitmentId = StrictHash;
+
+    fn commit_encode(&self, _: &mut CommitEngine) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CommitmentProtocol;
+
+    #[test]
+    fn zero_sized() {
+        let empty = StrictHash::from(CommitEngine::new(StrictHash::TAG).finish());
+        assert_eq!(().commit_id(), empty);
+        assert_eq!(PhantomData::<u64>.commit_id(), empty);
+        assert!(PhantomData::<u64>.commit().into_layout().is_empty());
+
+        let mut plain = CommitEngine::new("test");
+        plain.commit_to_raw(&[0xde, 0xad]);
+        let mut marked = plain.clone();
+        PhantomData::<u64>.commit_encode(&mut marked);
+        ().commit_encode(&mut marked);
+        assert_eq!(StrictHash::from(marked.finish()), StrictHash::from(plain.finish()));
+    }
+
+    #[test]
+    fn dag_cache() {
+        use std::cell::Cell;
+
+        #[derive(Debug)]
+        struct Node {
+            value: u8,
+            children: Vec<Rc<Node>>,
+            commits: Cell<usize>,
+        }
+
+        impl Node {
+            fn new(value: u8, children: &[&Rc<Node>]) -> Rc<Self> {
+                Rc::new(Node {
+                    value,
+                    children: children.iter().copied().cloned().collect(),
+                    commits: Cell::new(0),
+                })
+            }
+        }
+
+        impl CommitEncode for Node {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) {
+                let children = self
+                    .children
+                    .iter()
+                    .map(|child| child.commit_id())
+                    .collect::<Vec<_>>();
+                self.commit_encode_with(e, &children);
+            }
+        }
+
+        impl DagCommit for Node {
+            fn children(&self) -> impl Iterator<Item = &Rc<Self>> { self.children.iter() }
+            fn commit_encode_with(&self, e: &mut CommitEngine, children: &[StrictHash]) {
+                self.commits.set(self.commits.get() + 1);
+                e.commit_to_raw(&[self.value]);
+                for id in children {
+                    e.commit_to_raw(id.as_slice());
+                }
+            }
+        }
+
+        // Diamond: `top` references `left` and `right`, both referencing `root`
+        let root = Node::new(0, &[]);
+        let left = Node::new(1, &[&root]);
+        let right = Node::new(2, &[&root]);
+        let top = Node::new(3, &[&left, &right]);
+
+        let uncached = top.commit_id();
+        assert_eq!(root.commits.get(), 2);
+        root.commits.set(0);
+
+        let mut cache = CommitCache::new();
+        assert_eq!(cache.commit_id(&top), uncached);
+        assert_eq!(root.commits.get(), 1);
+        assert_eq!(top.commits.get(), 2);
+        assert_eq!(cache.len(), 4);
+        assert_eq!(cache.commit_id(&left), left.commit_id());
+        assert_eq!(root.commits.get(), 2);
+    }
+
+    #[test]
+    fn reveal_bundle() {
+        #[derive(Clone, Eq, PartialEq, Debug)]
+        #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+        #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+        struct Name(u64);
+
+        #[derive(Clone, Eq, PartialEq, Debug)]
+        #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+        #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+        struct Age(u64);
+
+        #[derive(Clone, Eq, PartialEq, Debug)]
+        #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+        #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+        struct Balance(u64);
+
+        impl CommitEncode for Name {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) { e.commit_to_serialized(self); }
+        }
+        impl CommitEncode for Age {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) { e.commit_to_serialized(self); }
+        }
+        impl CommitEncode for Balance {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) { e.commit_to_serialized(self); }
+        }
+
+        #[derive(Clone)]
+        struct Account {
+            name: Name,
+            age: Age,
+            balance: Balance,
+        }
+        impl StrictDumb for Account {
+            fn strict_dumb() -> Self {
+                Self {
+                    name: Name(0),
+                    age: Age(0),
+                    balance: Balance(0),
+                }
+            }
+        }
+
+        impl CommitEncode for Account {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) {
+                e.commit_to_hash(&self.name);
+                e.commit_to_hash(&self.age);
+                e.commit_to_hash(&self.balance);
+            }
+        }
+
+        let account = Account {
+            name: Name(0x1111_1111_1111_1111),
+            age: Age(0x2222_2222_2222_2222),
+            balance: Balance(0x3333_3333_3333_3333),
+        };
+        let id = account.commit_id();
+        let bundle = RevealBundle::new(&account);
+        assert!(bundle.verify(id));
+        assert!(bundle.verify_reveal(id, &Age(0x2222_2222_2222_2222)));
+        assert!(!bundle.verify_reveal(id, &Age(0)));
+        assert!(!bundle.verify_reveal(Name(0).commit_id(), &account.age));
+        // Value of another field with the same commitment id is not a reveal
+        assert_eq!(Age(account.name.0).commit_id(), account.name.commit_id());
+        assert!(!bundle.verify_reveal(id, &Age(account.name.0)));
+        assert!(!bundle.verify_reveal(id, &Age(account.balance.0)));
+
+        // Re-chunking the same data to point the field to another window doesn't
+        // produce a reveal
+        let data = bundle.transcript.chunks.concat();
+        let mut tampered = bundle.clone();
+        tampered.transcript.chunks = vec![vec![], data[..32].to_vec(), data[32..].to_vec()];
+        assert!(tampered.verify(id));
+        assert_eq!(tampered.transcript.step_chunks(1).next(), Some(&data[..32]));
+        assert!(!tampered.verify_reveal(id, &Age(account.name.0)));
+        assert!(tampered.verify_reveal(id, &account.age));
+
+        let hidden = [account.name.0.to_le_bytes(), account.balance.0.to_le_bytes()];
+        for chunk in bundle.transcript().chunks() {
+            for secret in &hidden {
+                assert!(!chunk.windows(8).any(|window| window == secret));
+            }
+        }
+
+        struct Pair(Age, Age);
+        impl StrictDumb for Pair {
+            fn strict_dumb() -> Self { Self(Age(0), Age(0)) }
+        }
+        impl CommitEncode for Pair {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) {
+                e.commit_to_hash(&self.0);
+                e.commit_to_hash(&self.1);
+            }
+        }
+        let pair = Pair(Age(1), Age(2));
+        let bundle = RevealBundle::new(&pair);
+        assert!(bundle.verify(pair.commit_id()));
+        assert!(!bundle.verify_reveal(pair.commit_id(), &Age(1)));
+
+        // Position of the fields following fixed-size data is derived
+        struct Tagged(Age);
+        impl StrictDumb for Tagged {
+            fn strict_dumb() -> Self { Self(Age(0)) }
+        }
+        impl CommitEncode for Tagged {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) {
+                e.commit_to_domain::<Age>();
+                e.commit_to_raw(b"tag");
+                e.commit_to_variant::<Age>(1);
+                e.commit_to_hash(&self.0);
+            }
+        }
+        let tagged = Tagged(Age(2));
+        let bundle = RevealBundle::new(&tagged);
+        assert!(bundle.verify_reveal(tagged.commit_id(), &Age(2)));
+        assert!(!bundle.verify_reveal(tagged.commit_id(), &Age(3)));
+
+        // Position of the fields following variable-size data can't be derived
+        struct Labeled(Name, Age);
+        impl StrictDumb for Labeled {
+            fn strict_dumb() -> Self { Self(Name(0), Age(0)) }
+        }
+        impl CommitEncode for Labeled {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) {
+                e.commit_to_serialized(&self.0);
+                e.commit_to_hash(&self.1);
+            }
+        }
+        let labeled = Labeled(Name(1), Age(2));
+        let bundle = RevealBundle::new(&labeled);
+        assert!(bundle.verify(labeled.commit_id()));
+        assert!(!bundle.verify_reveal(labeled.commit_id(), &Age(2)));
+    }
+
+    #[test]
+    fn presence() {
+        struct Amount(u64);
+        impl CommitEncode for Amount {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) {
+                // Zero amount doesn't contribute to the commitment
+                if self.0 != 0 {
+                    e.commit_to_raw(&self.0.to_le_bytes());
+                }
+            }
+        }
+
+        assert_eq!(Amount(0).commit_id(), ().commit_id());
+        let absent = Presence::<Amount>::Absent.commit_id();
+        let zero = Presence::Present(Amount(0)).commit_id();
+        assert_ne!(absent, zero);
+        assert_ne!(absent, Amount(0).commit_id());
+        assert_ne!(zero, Presence::Present(Amount(1)).commit_id());
+
+        // Values with their own domain are committed by their ids
+        #[derive(Clone, Eq, PartialEq, Debug)]
+        #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+        #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+        struct Tagged(u64);
+        impl CommitEncode for Tagged {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) {
+                e.commit_to_domain::<Self>();
+                e.commit_to_serialized(self);
+            }
+        }
+        let present = Presence::Present(Tagged(0));
+        assert_ne!(present.commit_id(), Presence::<Tagged>::Absent.commit_id());
+        assert_ne!(present.commit_id(), Presence::Present(Tagged(1)).commit_id());
+        let mut engine = CommitEngine::new(StrictHash::TAG);
+        engine.commit_to_raw(&[&[0x01][..], Tagged(0).commit_id().as_slice()].concat());
+        assert_eq!(present.commit_id(), engine.finish().into());
+        assert_eq!(
+            present.commit().into_layout(),
+            Presence::<Tagged>::Absent.commit().into_layout()
+        );
+
+        assert!(matches!(Presence::from(Some(5u8)), Presence::Present(5)));
+        assert_eq!(Option::<u8>::from(Presence::Absent), None);
+    }
+
+    #[test]
+    fn network_tagged() {
+        use crate::{commit_for_network, NetworkTaggedProtocol};
+
+        let msg = Presence::Present(());
+        let mainnet = commit_for_network(&msg, NetworkTaggedProtocol::BITCOIN);
+        assert_eq!(mainnet, commit_for_network(&msg, NetworkTaggedProtocol::BITCOIN));
+        assert_ne!(mainnet, commit_for_network(&msg, NetworkTaggedProtocol::TESTNET3));
+        assert_ne!(mainnet, msg.commit_id());
+        assert_eq!(
+            mainnet,
+            commit_for_network(&msg, NetworkTaggedProtocol::with_magic([0xF9, 0xBE, 0xB4, 0xD9]))
+        );
+    }
+
+    #[test]
+    fn commitment32() {
+        fn roundtrip<T: Commitment32 + fmt::Debug + Eq>(value: T) -> [u8; 32] {
+            let bytes = value.to_byte_array();
+            assert_eq!(T::from_byte_array(bytes), value);
+            bytes
+        }
+
+        let hash = ().commit_id();
+        assert_eq!(roundtrip(hash), hash.to_byte_array());
+        assert_eq!(StrictHash::from_byte_array([7; 32]).as_slice(), &[7; 32]);
+
+        let commitment = mpc::Commitment::from([0xA5; 32]);
+        assert_eq!(roundtrip(commitment), [0xA5; 32]);
+        assert_eq!(roundtrip(MerkleHash::void(0u8, 0u8)), *MerkleHash::void(0u8, 0u8).as_slice());
+    }
+
+    #[test]
+    fn contextual() {
+        struct Network(&'static [u8]);
+        impl CommitmentProtocol for Network {}
+        impl ContextualProtocol for Network {
+            fn context(&self) -> &[u8] { self.0 }
+        }
+
+        #[derive(Clone, Eq, PartialEq, Debug)]
+        #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+        #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+        struct Data(u32);
+
+        impl CommitEncode for Data {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) { e.commit_to_serialized(self); }
+        }
+
+        let (mainnet, testnet) = (Network(b"mainnet"), Network(b"testnet"));
+        let data = Data(0xdeadbeef);
+        let id = StrictHash::commit_in(&mainnet, &data);
+        assert_eq!(id, StrictHash::commit_in(&Network(b"mainnet"), &data));
+        assert_ne!(id, StrictHash::commit_in(&testnet, &data));
+        assert_ne!(id, StrictHash::commit_in(&mainnet, &Data(0)));
+        assert_ne!(id, data.commit_id());
+        assert_eq!(id.verify_in(&mainnet, &data), Ok(()));
+        assert!(id.verify_in(&testnet, &data).is_err());
+    }
+
+    #[test]
+    fn raw() {
+        let commit = |regions: &[&[u8]]| {
+            let mut engine = CommitEngine::new(StrictHash::TAG);
+            regions
+                .iter()
+                .for_each(|region| engine.commit_to_raw(region));
+            StrictHash::from(engine.finish())
+        };
+        assert_ne!(commit(&[b"ab", b"c"]), commit(&[b"abc"]));
+        assert_ne!(commit(&[b"", b"abc"]), commit(&[b"abc"]));
+        assert_eq!(commit(&[b"ab", b"c"]), commit(&[b"ab", b"c"]));
+    }
+
+    #[test]
+    fn nonzero() {
+        let chunk = |value: Option<NonZeroU32>| {
+            let mut engine = CommitEngine::with_transcript(StrictHash::TAG);
+            engine.commit_to_nonzero(value);
+            let transcript = engine.into_transcript().unwrap();
+            assert_eq!(transcript.layout().as_slice(), &[CommitStep::NonZero(32)]);
+            transcript.chunks()[0].clone()
+        };
+        assert_eq!(chunk(None), vec![0, 0, 0, 0]);
+        assert_eq!(chunk(NonZeroU32::new(5)), vec![5, 0, 0, 0]);
+
+        let mut engine = CommitEngine::with_transcript(StrictHash::TAG);
+        engine.commit_to_nonzero(NonZeroU8::new(5));
+        engine.commit_to_nonzero(None::<NonZeroU8>);
+        assert_eq!(engine.into_transcript().unwrap().chunks(), &[vec![5], vec![0]]);
+    }
+
+    #[test]
+    fn resume() {
+        #[derive(Clone, Eq, PartialEq, Debug)]
+        #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+        #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+        struct Field(u32);
+
+        let mut engine = CommitEngine::with_transcript(StrictHash::TAG);
+        engine.set_field_count_guard();
+        engine.commit_to_serialized(&Field(1));
+        engine.commit_to_raw(b"data");
+        engine.commit_to_serialized(&Field(2));
+        let (hash, layout) = engine.finish_layout();
+
+        let mut engine = CommitEngine::with_transcript(StrictHash::TAG);
+        engine.set_field_count_guard();
+        engine.commit_to_serialized(&Field(1));
+        let data = engine.to_bytes().unwrap();
+        let mut engine = CommitEngine::from_bytes(StrictHash::TAG, &data).unwrap();
+        engine.commit_to_raw(b"data");
+        let data = engine.to_bytes().unwrap();
+        let mut engine = CommitEngine::from_bytes(StrictHash::TAG, &data).unwrap();
+        engine.commit_to_serialized(&Field(2));
+        let (resumed, resumed_layout) = engine.finish_layout();
+
+        assert_eq!(StrictHash::from(resumed), StrictHash::from(hash));
+        assert_eq!(resumed_layout, layout);
+        assert!(CommitEngine::from_bytes(MerkleHash::TAG, &data).is_err());
+        assert!(CommitEngine::from_bytes(StrictHash::TAG, &data[1..]).is_err());
+        assert_eq!(CommitEngine::new(StrictHash::TAG).to_bytes(), None);
+    }
+
+    #[test]
+    fn transcript() {
+        #[derive(Clone, Eq, PartialEq, Debug)]
+        #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+        #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+        struct Data(u32, u8);
+
+        impl CommitEncode for Data {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) {
+                e.set_field_count_guard();
+                e.commit_to_serialized(self);
+                e.commit_to_serialized(&Data(self.1 as u32, 0));
+            }
+        }
+
+        let data = Data(0xdeadbeef, 7);
+        let id = data.commit_id();
+        let transcript = data.commit_transcript();
+        assert_eq!(transcript.tag(), StrictHash::TAG);
+        assert_eq!(transcript.layout(), &data.commit().into_layout());
+        assert_eq!(transcript.chunks().len(), 3);
+        assert!(transcript.verify_against::<Data>(id));
+        assert!(!transcript.verify_against::<Data>(Data(0xdeadbeef, 8).commit_id()));
+
+        let mut tampered = transcript.clone();
+        tampered.chunks[0][0] ^= 1;
+        assert!(!tampered.verify_against::<Data>(id));
+
+        let mut tampered = transcript.clone();
+        tampered.chunks.pop();
+        assert!(!tampered.verify_against::<Data>(id));
+
+        // Layout which is not the one of the type doesn't verify
+        let mut tampered = transcript.clone();
+        tampered.layout = tiny_vec![CommitStep::Raw];
+        assert!(!tampered.verify_against::<Data>(id));
+
+        #[derive(Clone, Eq, PartialEq, Debug)]
+        #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+        #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+        struct Other(u32, u8);
+        impl CommitEncode for Other {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) {
+                e.set_field_count_guard();
+                e.commit_to_raw(&[]);
+                e.commit_to_raw(&[]);
+            }
+        }
+        assert!(!transcript.verify_against::<Other>(id));
+    }
+
+    #[test]
+    fn committed() {
+        #[derive(Clone, Eq, PartialEq, Debug)]
+        #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+        #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+        struct Data(u32, u8);
+
+        impl CommitEncode for Data {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) { e.commit_to_serialized(self); }
+        }
+        impl StrictSerialize for Data {}
+        impl StrictDeserialize for Data {}
+
+        let mut committed = Committed::new(Data(0xdeadbeef, 7));
+        assert!(!committed.is_cached());
+        let id = committed.commit_id();
+        assert!(committed.is_cached());
+        assert_eq!(id, Data(0xdeadbeef, 7).commit_id());
+        assert_eq!(CommitId::commit_id(&committed), id);
+
+        committed.get_mut().1 = 8;
+        committed.invalidate();
+        assert!(!committed.is_cached());
+        assert_ne!(committed.commit_id(), id);
+        assert_eq!(committed.commit_id(), Data(0xdeadbeef, 8).commit_id());
+
+        let data = committed.to_strict_serialized::<U64MAX>().unwrap();
+        assert_eq!(
+            data,
+            Data(0xdeadbeef, 8)
+                .to_strict_serialized::<U64MAX>()
+                .unwrap()
+        );
+        let decoded = Committed::<Data>::from_strict_serialized::<U64MAX>(data).unwrap();
+        assert!(!decoded.is_cached());
+        assert_eq!(decoded, committed);
+    }
+
+    #[test]
+    fn domain_separation() {
+        #[derive(Clone, Eq, PartialEq, Debug)]
+        #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+        #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+        struct First(u32, u8);
+
+        #[derive(Clone, Eq, PartialEq, Debug)]
+        #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+        #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+        struct Second(u32, u8);
+
+        impl CommitEncode for First {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) {
+                e.commit_to_domain::<Self>();
+                e.commit_to_serialized(self);
+            }
+        }
+
+        impl CommitEncode for Second {
+            type CommitmentId = StrictHash;
+            fn commit_encode(&self, e: &mut CommitEngine) {
+                e.commit_to_domain::<Self>();
+                e.commit_to_serialized(self);
+            }
+        }
+
+        assert_ne!(First(0xdeadbeef, 7).commit_id(), Second(0xdeadbeef, 7).commit_id());
+        assert_eq!(First(0xdeadbeef, 7).commit_id(), First(0xdeadbeef, 7).commit_id());
+        assert_eq!(
+            First::commitment_layout().fields()[0],
+            CommitStep::Domain(TypeFqn::from("CommitVerify.First"))
+        );
+    }
+
+    #[test]
+    fn field_count_guard() {
+        #[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+        #[derive(StrictType, StrictEncode, StrictDecode)]
+        #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+        struct Field(u32);
+
+        #[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+        #[derive(StrictType, StrictEncode, StrictDecode)]
+        #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+        struct Pair(u32, u32);
+
+        fn commit(guard: bool, fields: &[u32]) -> StrictHash {
+            let mut engine = CommitEngine::new(StrictHash::TAG);
+            if guard {
+                engine.set_field_count_guard();
+            }
+            for field in fields {
+                engine.commit_to_serialized(&Field(*field));
+            }
+            engine.finish().into()
+        }
+
+        assert_ne!(commit(true, &[1, 2]), commit(true, &[1, 2, 3]));
+        assert_ne!(commit(false, &[1, 2]), commit(true, &[1, 2]));
+
+        // Two fields serialized as a single step produce the same data as when
+        // they are committed one by one, which is detected only by the guard.
+        let mut engine = CommitEngine::with_field_count_guard(StrictHash::TAG);
+        engine.commit_to_serialized(&Pair(1, 2));
+        let pair = StrictHash::from(engine.finish());
+        assert_ne!(pair, commit(true, &[1, 2]));
+
+        let mut engine = CommitEngine::new(StrictHash::TAG);
+        engine.commit_to_serialized(&Pair(1, 2));
+        assert_eq!(StrictHash::from(engine.finish()), commit(false, &[1, 2]));
+    }
+}
@@ -0,0 +1,171 @@
+// Client-side-validation foundation libraries.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Commitments to sets of non-overlapping intervals.
+
+use std::ops::Range;
+
+use strict_encoding::{StreamWriter, StrictEncode};
+
+use crate::{CommitEncode, CommitEngine, StrictHash};
+
+/// Policy of handling overlapping intervals during [`IntervalSet`]
+/// construction.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, Display)]
+#[display(lowercase)]
+pub enum OverlapPolicy {
+    /// Overlapping intervals are merged together.
+    #[default]
+    Merge,
+    /// Overlapping intervals are rejected with [`IntervalError::Overlap`].
+    Reject,
+}
+
+/// Errors constructing [`IntervalSet`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum IntervalError {
+    /// interval #{0} has its start greater than its end.
+    InvalidInterval(usize),
+
+    /// interval #{0} overlaps with another interval.
+    Overlap(usize),
+}
+
+/// Set of half-open intervals kept in the normalized form: sorted, with no
+/// empty intervals and with overlapping and adjacent intervals merged, such
+/// that two sets covering the same values are equal and commit identically.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct IntervalSet<T: Ord>(Vec<Range<T>>);
+
+impl<T: Ord> Default for IntervalSet<T> {
+    fn default() -> Self { Self(vec![]) }
+}
+
+impl<T: Ord + Clone> IntervalSet<T> {
+    /// Constructs normalized set out of the given intervals, handling
+    /// overlapping intervals according to the `policy`. Adjacent intervals
+    /// (where one ends at the start of the other) don't overlap and are
+    /// always merged; empty intervals are ignored.
+    pub fn with(
+        intervals: impl IntoIterator<Item = Range<T>>,
+        policy: OverlapPolicy,
+    ) -> Result<Self, IntervalError> {
+        let mut intervals = intervals.into_iter().enumerate().collect::<Vec<_>>();
+        if let Some((index, _)) = intervals.iter().find(|(_, range)| range.start > range.end) {
+            return Err(IntervalError::InvalidInterval(*index));
+        }
+        intervals.retain(|(_, range)| !range.is_empty());
+        intervals.sort_by(|(_, a), (_, b)| a.start.cmp(&b.start));
+
+        let mut normalized = Vec::<Range<T>>::with_capacity(intervals.len());
+        for (index, range) in intervals {
+            match normalized.last_mut() {
+                Some(last) if range.start < last.end && policy == OverlapPolicy::Reject => {
+                    return Err(IntervalError::Overlap(index));
+                }
+                Some(last) if range.start <= last.end => {
+                    if range.end > last.end {
+                        last.end = range.end;
+                    }
+                }
+                _ => normalized.push(range),
+            }
+        }
+        Ok(Self(normalized))
+    }
+
+    /// Detects whether the set covers the value.
+    pub fn contains(&self, value: &T) -> bool { self.0.iter().any(|range| range.contains(value)) }
+}
+
+impl<T: Ord> IntervalSet<T> {
+    /// Returns normalized intervals of the set.
+    pub fn intervals(&self) -> &[Range<T>] { &self.0 }
+
+    /// Returns number of the normalized intervals in the set.
+    pub fn len(&self) -> usize { self.0.len() }
+
+    /// Detects whether the set is empty.
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+}
+
+/// Commits to the number of the normalized intervals (as 32-bit
+/// little-endian integer), followed by the strict-serialized start and end of
+/// each of the intervals.
+impl<T: Ord + StrictEncode> CommitEncode for IntervalSet<T> {
+    type CommitmentId = StrictHash;
+
+    fn commit_encode(&self, e: &mut CommitEngine) {
+        let len = u32::try_from(self.0.len()).expect("too many intervals");
+        let mut data = len.to_le_bytes().to_vec();
+        for range in &self.0 {
+            for bound in [&range.start, &range.end] {
+                let ok = bound
+                    .strict_write(StreamWriter::new::<{ usize::MAX }>(&mut data))
+                    .is_ok();
+                debug_assert!(ok);
+            }
+        }
+        e.commit_to_raw(&data);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod test {
+    use super::*;
+    use crate::CommitId;
+
+    #[test]
+    fn normalization() {
+        let adjacent = IntervalSet::with([3..5u64, 1..3], OverlapPolicy::Reject).unwrap();
+        let single = IntervalSet::with([1..5u64], OverlapPolicy::Reject).unwrap();
+        assert_eq!(adjacent, single);
+        assert_eq!(adjacent.intervals(), &[1..5]);
+        assert_eq!(adjacent.commit_id(), single.commit_id());
+
+        let merged = IntervalSet::with([4..8u64, 1..5, 6..7, 9..9], OverlapPolicy::Merge).unwrap();
+        assert_eq!(merged.intervals(), &[1..8]);
+        assert!(merged.contains(&7));
+        assert!(!merged.contains(&8));
+        assert_ne!(merged.commit_id(), single.commit_id());
+
+        let split = IntervalSet::with([1..2u64, 3..5], OverlapPolicy::Merge).unwrap();
+        assert_eq!(split.len(), 2);
+        assert_ne!(split.commit_id(), single.commit_id());
+    }
+
+    #[test]
+    fn policy() {
+        assert_eq!(
+            IntervalSet::with([4..8u64, 1..5], OverlapPolicy::Reject),
+            Err(IntervalError::Overlap(0))
+        );
+        assert_eq!(
+            IntervalSet::with([1..5u64, Range { start: 7, end: 6 }], OverlapPolicy::Merge),
+            Err(IntervalError::InvalidInterval(1))
+        );
+        assert!(IntervalSet::<u64>::with([], OverlapPolicy::Reject)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,265 @@
+// Client-side-validation foundation libraries.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Commitments to JSON documents in their canonical form.
+//!
+//! JSON allows the same document to be written in many ways, thus the
+//! document is first converted into the canonical form, which is committed
+//! to. The canonical form is defined by the following rules:
+//! - no insignificant whitespace is present between the tokens;
+//! - object keys are sorted by the byte-wise order of their UTF-8 encoding;
+//!   duplicated keys are resolved by the JSON parser before the
+//!   canonicalization;
+//! - strings (including object keys) are escaped by replacing `"` and `\` with
+//!   `\"` and `\\`, the `\b`, `\f`, `\n`, `\r` and `\t` control characters with
+//!   their short escapes and the rest of the control characters with `\u00XX`
+//!   escapes using lowercase hex digits; all other characters are written as-is
+//!   in UTF-8;
+//! - numbers are converted into 64-bit floating-point numbers and written as
+//!   ECMAScript `Number.prototype.toString` does, as required by [RFC 8785]:
+//!   `-0` is written as `0`; the shortest sequence of decimal digits which
+//!   parses back into the same floating-point number is used, written as a
+//!   decimal integer or fraction if its decimal exponent is in the range from
+//!   -7 to 20 (like `100`, `2.5` or `0.000001`), and in exponent notation with
+//!   an explicit exponent sign otherwise (like `1e+21` or `1e-7`). Thus, the
+//!   form of the number doesn't depend on whether it was written as an integer
+//!   or a floating-point number (`1`, `1.0` and `1e0` are all written as `1`),
+//!   and integers above 2^53 are rounded to the nearest floating-point number;
+//!   numbers outside the floating-point range can't be canonicalized;
+//! - `true`, `false` and `null` are written as-is.
+//!
+//! [RFC 8785]: https://www.rfc-editor.org/rfc/rfc8785
+
+use serde_json::{Number, Value};
+
+use crate::{CommitEncode, CommitEngine, StrictHash};
+
+/// Errors converting JSON document into the canonical form.
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum CanonicalJsonError {
+    /// invalid JSON document: {0}
+    #[from]
+    Json(serde_json::Error),
+
+    /// number {0} can't be represented as a 64-bit floating-point number.
+    NumberRange(String),
+}
+
+/// JSON document in its canonical form (see the [module](self) documentation
+/// for the canonicalization rules).
+///
+/// Commits to the UTF-8 bytes of the canonical form.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[display(inner)]
+pub struct CanonicalJson(String);
+
+impl TryFrom<&Value> for CanonicalJson {
+    type Error = CanonicalJsonError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        let mut s = String::new();
+        write_value(&mut s, value)?;
+        Ok(Self(s))
+    }
+}
+
+impl CanonicalJson {
+    /// Parses JSON document and converts it into the canonical form.
+    pub fn parse(json: &str) -> Result<Self, CanonicalJsonError> {
+        let value = serde_json::from_str::<Value>(json)?;
+        Self::try_from(&value)
+    }
+
+    /// Returns the canonical form of the document.
+    pub fn as_str(&self) -> &str { &self.0 }
+}
+
+impl CommitEncode for CanonicalJson {
+    type CommitmentId = StrictHash;
+
+    fn commit_encode(&self, e: &mut CommitEngine) { e.commit_to_raw(self.0.as_bytes()); }
+}
+
+fn write_value(s: &mut String, value: &Value) -> Result<(), CanonicalJsonError> {
+    match value {
+        Value::Null => s.push_str("null"),
+        Value::Bool(true) => s.push_str("true"),
+        Value::Bool(false) => s.push_str("false"),
+        Value::Number(number) => write_number(s, number)?,
+        Value::String(string) => write_string(s, string),
+        Value::Array(array) => {
+            s.push('[');
+            for (index, item) in array.iter().enumerate() {
+                if index > 0 {
+                    s.push(',');
+                }
+                write_value(s, item)?;
+            }
+            s.push(']');
+        }
+        Value::Object(object) => {
+            let mut entries = object.iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+            s.push('{');
+            for (index, (key, item)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    s.push(',');
+                }
+                write_string(s, key);
+                s.push(':');
+                write_value(s, item)?;
+            }
+            s.push('}');
+        }
+    }
+    Ok(())
+}
+
+/// Writes number following ECMAScript `Number::toString(10)` algorithm.
+fn write_number(s: &mut String, number: &Number) -> Result<(), CanonicalJsonError> {
+    let float = number
+        .as_f64()
+        .filter(|float| float.is_finite())
+        .ok_or_else(|| CanonicalJsonError::NumberRange(number.to_string()))?;
+    if float == 0.0 {
+        s.push('0');
+        return Ok(());
+    }
+    if float < 0.0 {
+        s.push('-');
+    }
+    // Rust formats floats with the shortest sequence of digits which parses
+    // back into the same number
+    let sci = format!("{:e}", float.abs());
+    let (mantissa, exp) = sci.split_once('e').expect("exponent notation");
+    let digits = mantissa.replace('.', "");
+    let exp = exp.parse::<i32>().expect("decimal exponent");
+    let k = digits.len() as i32;
+    let n = exp + 1;
+    if k <= n && n <= 21 {
+        s.push_str(&digits);
+        s.extend((0..n - k).map(|_| '0'));
+    } else if 0 < n && n <= 21 {
+        s.push_str(&digits[..n as usize]);
+        s.push('.');
+        s.push_str(&digits[n as usize..]);
+    } else if -6 < n && n <= 0 {
+        s.push_str("0.");
+        s.extend((0..-n).map(|_| '0'));
+        s.push_str(&digits);
+    } else {
+        s.push_str(&digits[..1]);
+        if k > 1 {
+            s.push('.');
+            s.push_str(&digits[1..]);
+        }
+        s.push('e');
+        s.push(if n > 0 { '+' } else { '-' });
+        s.push_str(&(n - 1).abs().to_string());
+    }
+    Ok(())
+}
+
+fn write_string(s: &mut String, string: &str) {
+    s.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\u{08}' => s.push_str("\\b"),
+            '\u{0C}' => s.push_str("\\f"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            c if c < ' ' => s.push_str(&format!("\\u{:04x}", c as u32)),
+            c => s.push(c),
+        }
+    }
+    s.push('"');
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CommitId;
+
+    #[test]
+    fn canonical_form() {
+        let json = CanonicalJson::parse(
+            r#"{ "b": [1.0, -0.0, 2.5, 1e2, 1e300],
+                 "a": { "y": null, "x": true },
+                 "c": "line\nquote\"\u0001é" }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            json.as_str(),
+            r#"{"a":{"x":true,"y":null},"b":[1,0,2.5,100,1e+300],"c":"line\nquote\"\u0001é"}"#
+        );
+        assert_eq!(CanonicalJson::parse(json.as_str()).unwrap(), json);
+    }
+
+    #[test]
+    fn numbers() {
+        fn canonical(json: &str) -> String { CanonicalJson::parse(json).unwrap().0 }
+
+        assert_eq!(canonical("1e16"), "10000000000000000");
+        assert_eq!(canonical("10000000000000000"), "10000000000000000");
+        assert_eq!(canonical("1.0"), "1");
+        assert_eq!(canonical("1"), "1");
+        assert_eq!(canonical("-0"), "0");
+        assert_eq!(canonical("-0.0"), "0");
+        assert_eq!(canonical("18446744073709551615"), "18446744073709552000");
+        assert_eq!(canonical("-9223372036854775808"), "-9223372036854776000");
+        assert_eq!(canonical("1e21"), "1e+21");
+        assert_eq!(canonical("123e20"), "1.23e+22");
+        assert_eq!(canonical("0.000001"), "0.000001");
+        assert_eq!(canonical("1e-7"), "1e-7");
+        assert_eq!(canonical("-1.5e-10"), "-1.5e-10");
+        assert_eq!(canonical("0.1"), "0.1");
+        assert_eq!(canonical("123.456"), "123.456");
+        assert_eq!(canonical("4.35"), "4.35");
+        assert_eq!(canonical("5e-324"), "5e-324");
+        assert_eq!(canonical("1.7976931348623157e308"), "1.7976931348623157e+308");
+    }
+
+    #[test]
+    fn commitment() {
+        let compact =
+            CanonicalJson::parse(r#"{"name":"asset","precision":8,"tags":["a","b"]}"#).unwrap();
+        let formatted = CanonicalJson::parse(
+            r#"{
+                "tags": [ "a", "b" ],
+                "precision": 8.0,
+                "name": "asset"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(compact.commit_id(), formatted.commit_id());
+
+        let reordered =
+            CanonicalJson::parse(r#"{"name":"asset","precision":8,"tags":["b","a"]}"#).unwrap();
+        let changed =
+            CanonicalJson::parse(r#"{"name":"asset","precision":9,"tags":["a","b"]}"#).unwrap();
+        assert_ne!(compact.commit_id(), reordered.commit_id());
+        assert_ne!(compact.commit_id(), changed.commit_id());
+    }
+}
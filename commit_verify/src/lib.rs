@@ -44,29 +44,49 @@ extern crate serde;
 #[cfg(feature = "derive")]
 pub use commit_encoding_derive::CommitEncode;
 
+mod accumulator;
 mod commit;
 mod conceal;
 mod convolve;
 mod embed;
 mod id;
+mod interval;
+mod threshold;
 #[cfg(feature = "stl")]
 pub mod stl;
 
+#[cfg(feature = "serde_json")]
+pub mod json;
 pub mod merkle;
+pub mod mmr;
 pub mod mpc;
 mod digest;
 pub mod vesper;
 
-pub use commit::{CommitVerify, TryCommitVerify, VerifyError};
-pub use conceal::Conceal;
+pub use accumulator::{XorAccumulator, XorWitness, XOR_ELEMENT_TAG, XOR_ROOT_TAG};
+pub use commit::{
+    commit_for_network, CommitVerify, ContextualCommit, ContextualProtocol, DynCommit, DynScheme,
+    NetworkTaggedProtocol, TryCommitVerify, VerifyError,
+};
+pub use conceal::{Conceal, ConcealAll, ConcealByHash};
 pub use convolve::{ConvolveCommit, ConvolveCommitProof, ConvolveVerifyError};
 pub use digest::{Digest, DigestExt, Ripemd160, Sha256};
-pub use embed::{EmbedCommitProof, EmbedCommitVerify, EmbedVerifyError, VerifyEq};
+pub use embed::{
+    EmbedCommitProof, EmbedCommitRegion, EmbedCommitVerify, EmbedVerifyError, VerifyEq,
+};
 pub use id::{
-    CommitColType, CommitEncode, CommitEngine, CommitId, CommitLayout, CommitStep, CommitmentId,
-    CommitmentLayout, StrictHash,
+    type_sem_id, CommitCache, CommitColType, CommitEncode, CommitEngine, CommitId, CommitLayout,
+    CommitStep, CommitTranscript, Commitment32, CommitmentId, CommitmentLayout, CommitmentSchema,
+    Committed, DagCommit, NonZeroInt, RevealBundle, StrictHash,
+};
+pub use interval::{IntervalError, IntervalSet, OverlapPolicy};
+pub use merkle::{
+    build_merkle, commit_pair, merkle_commit, Chirality, MerkleBuoy, MerkleHash, MerkleLeaves,
+    MerkleNode, MerklePath, MerkleStrategy, MerkleTreeHandle, NodeBranching, MERKLE_HASH_PREFIX,
+    MERKLE_PAIR_TAG,
 };
-pub use merkle::{MerkleBuoy, MerkleHash, MerkleLeaves, MerkleNode, NodeBranching};
+pub use mmr::{AppendProof, CommitLog, MerkleMountainRange};
+pub use threshold::{ShareProof, ThresholdCommitment, ThresholdShare};
 
 pub const LIB_NAME_COMMIT_VERIFY: &str = "CommitVerify";
 
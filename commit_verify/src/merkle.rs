@@ -20,16 +20,18 @@
 // limitations under the License.
 
 use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
 use std::ops::SubAssign;
+use std::str::FromStr;
 
 use amplify::confinement::Confined;
 use amplify::num::{u256, u5};
-use amplify::{Bytes32, Wrapper};
+use amplify::{hex, Bytes32, Wrapper};
 use sha2::Sha256;
 use strict_encoding::StrictEncode;
 
 use crate::digest::DigestExt;
-use crate::{CommitId, CommitmentId, LIB_NAME_COMMIT_VERIFY};
+use crate::{CommitEncode, CommitId, CommitmentId, LIB_NAME_COMMIT_VERIFY};
 
 /// Type of merkle node branching.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
@@ -103,7 +105,7 @@ impl MerkleNode {
 ///
 /// [LNPBP-4]: https://github.com/LNP-BP/LNPBPs/blob/master/lnpbp-0004.md
 #[derive(Wrapper, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, From)]
-#[wrapper(Deref, BorrowSlice, Display, FromStr, Hex, Index, RangeOps)]
+#[wrapper(Deref, BorrowSlice, Hex, Index, RangeOps)]
 #[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
 #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
@@ -121,6 +123,31 @@ impl From<Sha256> for MerkleHash {
     fn from(hash: Sha256) -> Self { hash.finish().into() }
 }
 
+/// Prefix used in the alternate string representation of [`MerkleHash`].
+pub const MERKLE_HASH_PREFIX: &str = "mh:";
+
+/// Displays merkle hash as a hexadecimal string; with the alternate flag
+/// (`{:#}`) the string is prefixed with [`MERKLE_HASH_PREFIX`].
+impl Display for MerkleHash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.write_str(MERKLE_HASH_PREFIX)?;
+        }
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Parses merkle hash from a hexadecimal string, optionally prefixed with
+/// [`MERKLE_HASH_PREFIX`].
+impl FromStr for MerkleHash {
+    type Err = hex::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix(MERKLE_HASH_PREFIX).unwrap_or(s);
+        Bytes32::from_str(s).map(Self)
+    }
+}
+
 const VIRTUAL_LEAF: MerkleHash = MerkleHash(Bytes32::from_array([0xFF; 32]));
 
 impl MerkleHash {
@@ -148,7 +175,10 @@ impl MerkleHash {
     ///
     /// [LNPBP-81]: https://github.com/LNP-BP/LNPBPs/blob/master/lnpbp-0081.md
     pub fn merklize(leaves: &impl MerkleLeaves) -> Self {
-        let mut nodes = leaves.merkle_leaves().map(|leaf| leaf.commit_id());
+        Self::merklize_nodes(leaves.merkle_leaves().map(|leaf| leaf.commit_id()))
+    }
+
+    fn merklize_nodes(mut nodes: impl ExactSizeIterator<Item = MerkleHash>) -> Self {
         let base_width =
             u32::try_from(nodes.len()).expect("too many merkle leaves (more than 2^31)");
         if base_width == 1 {
@@ -195,6 +225,244 @@ impl MerkleHash {
     }
 }
 
+/// Tag used by [`commit_pair`].
+pub const MERKLE_PAIR_TAG: &str = "urn:ubideco:merkle:pair#2026-10-14";
+
+/// Side of a node within its parent.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[display(lowercase)]
+#[repr(u8)]
+pub enum Chirality {
+    /// Left-side node.
+    Left = 0x00,
+    /// Right-side node.
+    Right = 0x01,
+}
+
+/// Commits to an ordered pair of nodes of a custom tree-like structure, which
+/// is placed on the given `side` within its parent.
+///
+/// Both the order of the nodes and the side are committed to, such that
+/// swapping the nodes or changing the side changes the commitment. The hash is
+/// tagged with [`MERKLE_PAIR_TAG`], such that it never matches any of the
+/// [`MerkleNode`] commitments.
+pub fn commit_pair(left: MerkleHash, right: MerkleHash, side: Chirality) -> MerkleHash {
+    let mut engine = Sha256::from_tag(MERKLE_PAIR_TAG);
+    engine.input_u8(side as u8);
+    engine.input_raw(left.as_slice());
+    engine.input_raw(right.as_slice());
+    MerkleHash::from(engine)
+}
+
+/// Commits to an ordered list of messages by merklizing them according to the
+/// [LNPBP-81] procedure.
+///
+/// Returns the merkle root and the handle to the merkle tree, which can be used
+/// to produce inclusion proofs for each message.
+///
+/// [LNPBP-81]: https://github.com/LNP-BP/LNPBPs/blob/master/lnpbp-0081.md
+pub fn merkle_commit(
+    messages: impl IntoIterator<Item = impl CommitEncode<CommitmentId = MerkleHash>>,
+) -> (MerkleHash, MerkleTreeHandle) {
+    let handle = MerkleTreeHandle {
+        leaves: messages.into_iter().map(|msg| msg.commit_id()).collect(),
+    };
+    (handle.root(), handle)
+}
+
+/// Strategy of the merkle tree construction used by [`build_merkle`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, Display)]
+#[display(lowercase)]
+pub enum MerkleStrategy {
+    /// Tree width is padded to the nearest power of two, with the missing
+    /// leaves filled with virtual placeholder leaves.
+    Padded,
+    /// Unbalanced tree over exactly the provided leaves, as defined by
+    /// [LNPBP-81].
+    ///
+    /// [LNPBP-81]: https://github.com/LNP-BP/LNPBPs/blob/master/lnpbp-0081.md
+    #[default]
+    Compact,
+}
+
+/// Computes merkle root of an ordered list of messages, building either a
+/// padded or a compact tree according to the `strategy`.
+///
+/// The [`MerkleStrategy::Compact`] root is equal to the one produced by
+/// [`merkle_commit`]; in particular, the root of a single-leaf tree is the
+/// leaf hash itself. The roots for different strategies differ unless the
+/// number of the leaves is a power of two.
+///
+/// # Panics
+///
+/// If the number of leaves exceeds `2^31`.
+pub fn build_merkle(
+    messages: impl IntoIterator<Item = impl CommitEncode<CommitmentId = MerkleHash>>,
+    strategy: MerkleStrategy,
+) -> MerkleHash {
+    let mut leaves = messages
+        .into_iter()
+        .map(|msg| msg.commit_id())
+        .collect::<Vec<_>>();
+    if strategy == MerkleStrategy::Padded && !leaves.is_empty() {
+        let width = u32::try_from(leaves.len())
+            .ok()
+            .and_then(u32::checked_next_power_of_two)
+            .expect("too many merkle leaves (more than 2^31)");
+        leaves.resize(width as usize, VIRTUAL_LEAF);
+    }
+    MerkleHash::merklize_nodes(leaves.into_iter())
+}
+
+/// Merkle tree constructed according to the [LNPBP-81] merklization
+/// procedure, which keeps the hashes of its leaves and is able to produce
+/// inclusion proofs for each of them.
+///
+/// [LNPBP-81]: https://github.com/LNP-BP/LNPBPs/blob/master/lnpbp-0081.md
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct MerkleTreeHandle {
+    leaves: Vec<MerkleHash>,
+}
+
+impl MerkleTreeHandle {
+    /// Constructs merkle tree out of the merkle leaves.
+    pub fn with(leaves: &impl MerkleLeaves) -> Self {
+        Self {
+            leaves: leaves
+                .merkle_leaves()
+                .map(|leaf| leaf.commit_id())
+                .collect(),
+        }
+    }
+
+    /// Number of leaves in the tree.
+    pub fn width(&self) -> u32 {
+        u32::try_from(self.leaves.len()).expect("too many merkle leaves (more than 2^31)")
+    }
+
+    /// Computes merkle root of the tree, which equals to the one produced by
+    /// [`MerkleHash::merklize`] for the same leaves.
+    pub fn root(&self) -> MerkleHash { MerkleHash::merklize_nodes(self.leaves.iter().copied()) }
+
+    /// Produces inclusion proof for the leaf at the given `pos`, or `None` if
+    /// the position is outside the tree width.
+    pub fn proof(&self, pos: u32) -> Option<MerklePath> {
+        if pos >= self.width() {
+            return None;
+        }
+        let mut path = vec![];
+        if self.width() > 1 {
+            Self::_path(&self.leaves, pos, u5::ZERO, self.width(), &mut path);
+        }
+        Some(MerklePath {
+            pos,
+            width: self.width(),
+            path,
+        })
+    }
+
+    fn _path(
+        nodes: &[MerkleHash],
+        pos: u32,
+        depth: u5,
+        base_width: u32,
+        path: &mut Vec<MerkleHash>,
+    ) {
+        let branch_width = nodes.len() as u32;
+        if branch_width <= 2 {
+            if branch_width == 2 {
+                path.push(nodes[1 - pos as usize]);
+            }
+            return;
+        }
+        let div = branch_width / 2 + branch_width % 2;
+        let (left, right) = nodes.split_at(div as usize);
+        if pos < div {
+            path.push(MerkleHash::_merklize(
+                right.iter().copied(),
+                depth + 1,
+                branch_width - div,
+                base_width,
+            ));
+            Self::_path(left, pos, depth + 1, base_width, path);
+        } else {
+            path.push(MerkleHash::_merklize(left.iter().copied(), depth + 1, div, base_width));
+            Self::_path(right, pos - div, depth + 1, base_width, path);
+        }
+    }
+}
+
+/// Inclusion proof of a leaf in a merkle tree constructed according to the
+/// [LNPBP-81] merklization procedure.
+///
+/// The proof contains sibling node hashes from the root down to the leaf.
+///
+/// [LNPBP-81]: https://github.com/LNP-BP/LNPBPs/blob/master/lnpbp-0081.md
+#[derive(Getters, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct MerklePath {
+    /// Position of the leaf in the tree.
+    #[getter(as_copy)]
+    pos: u32,
+    /// Number of leaves in the tree.
+    #[getter(as_copy)]
+    width: u32,
+    /// Sibling node hashes, starting from the root.
+    path: Vec<MerkleHash>,
+}
+
+impl MerklePath {
+    /// Computes merkle root for the given `leaf` using the proof, or `None` if
+    /// the proof is malformed.
+    pub fn merkle_root(&self, leaf: MerkleHash) -> Option<MerkleHash> {
+        if self.pos >= self.width {
+            return None;
+        }
+        if self.width == 1 {
+            return self.path.is_empty().then_some(leaf);
+        }
+        let mut siblings = self.path.iter().copied();
+        let root = self._root(leaf, self.pos, u5::ZERO, self.width, &mut siblings)?;
+        siblings.next().is_none().then_some(root)
+    }
+
+    /// Verifies that the `leaf` is included into the tree with the given merkle
+    /// `root` at the proof position.
+    pub fn verify(&self, leaf: MerkleHash, root: MerkleHash) -> bool {
+        self.merkle_root(leaf) == Some(root)
+    }
+
+    fn _root(
+        &self,
+        leaf: MerkleHash,
+        pos: u32,
+        depth: u5,
+        branch_width: u32,
+        siblings: &mut impl Iterator<Item = MerkleHash>,
+    ) -> Option<MerkleHash> {
+        if branch_width == 1 {
+            return Some(MerkleHash::single(depth, self.width, leaf));
+        }
+        if branch_width == 2 {
+            let sibling = siblings.next()?;
+            return Some(if pos == 0 {
+                MerkleHash::branches(depth, self.width, leaf, sibling)
+            } else {
+                MerkleHash::branches(depth, self.width, sibling, leaf)
+            });
+        }
+        let div = branch_width / 2 + branch_width % 2;
+        let sibling = siblings.next()?;
+        let depth_next = depth.checked_add(1)?;
+        Some(if pos < div {
+            let node = self._root(leaf, pos, depth_next, div, siblings)?;
+            MerkleHash::branches(depth, self.width, node, sibling)
+        } else {
+            let node = self._root(leaf, pos - div, depth_next, branch_width - div, siblings)?;
+            MerkleHash::branches(depth, self.width, sibling, node)
+        })
+    }
+}
+
 pub trait MerkleLeaves {
     type Leaf: CommitId<CommitmentId = MerkleHash>;
     fn merkle_leaves(&self) -> impl ExactSizeIterator<Item = &Self::Leaf>;
@@ -297,3 +565,82 @@ impl<D: Copy + Eq + SubAssign<u8> + Default> MerkleBuoy<D> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use amplify::hex::ToHex;
+
+    use super::*;
+    use crate::mpc::Leaf;
+
+    #[test]
+    fn merkle_hash_display() {
+        let hash = MerkleHash::void(0u8, 1u8);
+        let s = hash.to_string();
+        assert_eq!(s, hash.to_hex());
+        assert_eq!(MerkleHash::from_str(&s), Ok(hash));
+
+        let s = format!("{hash:#}");
+        assert_eq!(s, format!("mh:{}", hash.to_hex()));
+        assert_eq!(MerkleHash::from_str(&s), Ok(hash));
+
+        assert!(MerkleHash::from_str("mh:00").is_err());
+        assert!(MerkleHash::from_str("mh").is_err());
+    }
+
+    #[test]
+    fn pair() {
+        let a = MerkleHash::void(0u8, 1u8);
+        let b = MerkleHash::void(0u8, 2u8);
+        let pair = commit_pair(a, b, Chirality::Left);
+        assert_eq!(pair, commit_pair(a, b, Chirality::Left));
+        assert_ne!(pair, commit_pair(b, a, Chirality::Left));
+        assert_ne!(pair, commit_pair(a, b, Chirality::Right));
+        assert_ne!(pair, MerkleHash::branches(0u8, 2u8, a, b));
+    }
+
+    #[test]
+    fn strategy() {
+        let leaves = |width: u32| (0..width).map(|pos| Leaf::entropy(0xdeadbeef, pos));
+
+        let leaf = Leaf::entropy(0xdeadbeef, 0).commit_id();
+        assert_eq!(build_merkle(leaves(1), MerkleStrategy::Compact), leaf);
+        assert_eq!(build_merkle(leaves(1), MerkleStrategy::Padded), leaf);
+
+        for width in 2..12 {
+            let compact = build_merkle(leaves(width), MerkleStrategy::Compact);
+            let padded = build_merkle(leaves(width), MerkleStrategy::Padded);
+            assert_eq!(compact, build_merkle(leaves(width), MerkleStrategy::Compact));
+            assert_eq!(padded, build_merkle(leaves(width), MerkleStrategy::Padded));
+            assert_eq!(compact, merkle_commit(leaves(width)).0);
+            if width.is_power_of_two() {
+                assert_eq!(compact, padded);
+            } else {
+                assert_ne!(compact, padded);
+            }
+        }
+    }
+
+    #[test]
+    fn merkle_commit_proofs() {
+        let (root, handle) = merkle_commit([] as [Leaf; 0]);
+        assert_eq!(root, MerkleHash::void(0u8, 0u8));
+        assert_eq!(handle.proof(0), None);
+
+        for width in 1..24u32 {
+            let leaves = (0..width).map(|pos| Leaf::entropy(0xdeadbeef, pos));
+            let (root, handle) = merkle_commit(leaves.clone());
+            let list = Confined::<Vec<_>, 0, { u8::MAX as usize }>::from_iter_checked(leaves);
+            assert_eq!(root, MerkleHash::merklize(&list));
+            assert_eq!(handle, MerkleTreeHandle::with(&list));
+            assert_eq!(handle.proof(width), None);
+
+            for (pos, leaf) in list.iter().enumerate() {
+                let proof = handle.proof(pos as u32).unwrap();
+                assert!(proof.verify(leaf.commit_id(), root));
+                let other = Leaf::entropy(0xdeadbeef, width);
+                assert!(!proof.verify(other.commit_id(), root));
+            }
+        }
+    }
+}
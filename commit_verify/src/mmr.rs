@@ -0,0 +1,368 @@
+// Client-side-validation foundation libraries.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Merkle mountain range (MMR) accumulator and append-only commitment log
+//! built on top of it.
+//!
+//! MMR nodes are hashed as [`MerkleNode`] branches with zero width, where the
+//! depth is the height of the node above the leaves; peaks are bagged from the
+//! right to the left into a root using branches with depth `0xFF` committing to
+//! the total number of leaves as the width.
+
+use std::marker::PhantomData;
+
+use amplify::num::u256;
+
+use crate::{CommitEncode, CommitId, MerkleHash, MerkleNode};
+
+const BAGGING_DEPTH: u8 = u8::MAX;
+
+fn mmr_branch(height: u8, left: MerkleHash, right: MerkleHash) -> MerkleHash {
+    MerkleHash::branches(height, u256::ZERO, left, right)
+}
+
+fn bag_peaks(len: u64, peaks: &[MerkleHash]) -> MerkleHash {
+    let mut iter = peaks.iter().rev().copied();
+    let Some(mut root) = iter.next() else {
+        return MerkleNode::void(0u8, u256::ZERO).commit_id();
+    };
+    for peak in iter {
+        root = MerkleHash::branches(BAGGING_DEPTH, len, peak, root);
+    }
+    root
+}
+
+/// Merkle mountain range: append-only accumulator of merkle leaves.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct MerkleMountainRange {
+    /// Nodes of each of the levels, starting from the leaves.
+    levels: Vec<Vec<MerkleHash>>,
+}
+
+impl MerkleMountainRange {
+    /// Constructs empty merkle mountain range.
+    pub fn new() -> Self { Self::default() }
+
+    /// Number of leaves in the range.
+    pub fn len(&self) -> u64 { self.levels.first().map(Vec::len).unwrap_or_default() as u64 }
+
+    /// Detects whether the range has no leaves.
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Returns peaks of the range, starting from the highest one.
+    pub fn peaks(&self) -> Vec<MerkleHash> {
+        self.levels
+            .iter()
+            .rev()
+            .filter(|level| level.len() % 2 == 1)
+            .filter_map(|level| level.last().copied())
+            .collect()
+    }
+
+    /// Computes root of the range by bagging its peaks.
+    pub fn root(&self) -> MerkleHash { bag_peaks(self.len(), &self.peaks()) }
+
+    /// Appends `leaf` to the range, returning the proof of its inclusion into
+    /// the updated range.
+    pub fn push(&mut self, leaf: MerkleHash) -> AppendProof {
+        let pos = self.len();
+        let mut node = leaf;
+        let mut height = 0usize;
+        loop {
+            if self.levels.len() == height {
+                self.levels.push(vec![]);
+            }
+            let level = &mut self.levels[height];
+            level.push(node);
+            if level.len() % 2 == 1 {
+                break;
+            }
+            node = mmr_branch(height as u8 + 1, level[level.len() - 2], level[level.len() - 1]);
+            height += 1;
+        }
+        self.prove(pos).expect("leaf was just added")
+    }
+
+    /// Produces proof of inclusion of the leaf at `pos` into the current state
+    /// of the range, or `None` if there is no such leaf.
+    pub fn prove(&self, pos: u64) -> Option<AppendProof> {
+        let len = self.len();
+        if pos >= len {
+            return None;
+        }
+        let (height, _) = peak_for(pos, len)?;
+        let path = (0..height)
+            .map(|level| self.levels[level as usize][((pos >> level) ^ 1) as usize])
+            .collect();
+        Some(AppendProof {
+            pos,
+            width: len,
+            path,
+            peaks: self.peaks(),
+        })
+    }
+
+    /// Updates `proof` produced for an earlier state of the range, such that
+    /// it verifies against the current root.
+    ///
+    /// Returns `None` if the proof is malformed (the leaf position is outside
+    /// of the proof range, or the range is wider than the current one), or if
+    /// the current range is not an extension of the range the proof was
+    /// produced for, i.e. if any of the peaks from the proof is not present in
+    /// the current range at its position.
+    pub fn update_proof(&self, proof: &AppendProof) -> Option<AppendProof> {
+        let width = proof.width;
+        if proof.pos >= width ||
+            width > self.len() ||
+            proof.peaks.len() != width.count_ones() as usize
+        {
+            return None;
+        }
+        let heights = (0..u64::BITS)
+            .rev()
+            .filter(|height| width & (1 << height) != 0);
+        for (height, peak) in heights.zip(&proof.peaks) {
+            let start = width & !(u64::MAX >> (u64::BITS - 1 - height));
+            let node = self
+                .levels
+                .get(height as usize)?
+                .get((start >> height) as usize)?;
+            if node != peak {
+                return None;
+            }
+        }
+        self.prove(proof.pos)
+    }
+}
+
+/// Finds height of the peak containing leaf at `pos` and the index of the peak,
+/// counting from the highest one.
+fn peak_for(pos: u64, len: u64) -> Option<(u32, usize)> {
+    let mut offset = 0u64;
+    let mut index = 0usize;
+    for height in (0..u64::BITS).rev() {
+        if len & (1 << height) == 0 {
+            continue;
+        }
+        offset += 1 << height;
+        if pos < offset {
+            return Some((height, index));
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Proof of inclusion of a leaf into a [`MerkleMountainRange`] with a given
+/// number of leaves.
+#[derive(Getters, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct AppendProof {
+    /// Position of the leaf in the range.
+    #[getter(as_copy)]
+    pos: u64,
+    /// Number of leaves in the range at the moment of the proof construction.
+    #[getter(as_copy)]
+    width: u64,
+    /// Sibling node hashes from the leaf up to its peak.
+    path: Vec<MerkleHash>,
+    /// Peaks of the range, starting from the highest one.
+    peaks: Vec<MerkleHash>,
+}
+
+impl AppendProof {
+    /// Computes root of the range for the given `leaf` using the proof, or
+    /// `None` if the proof is malformed.
+    pub fn mmr_root(&self, leaf: MerkleHash) -> Option<MerkleHash> {
+        let (height, index) = peak_for(self.pos, self.width)?;
+        if self.path.len() != height as usize ||
+            self.peaks.len() != self.width.count_ones() as usize
+        {
+            return None;
+        }
+        let peak = self
+            .path
+            .iter()
+            .enumerate()
+            .fold(leaf, |node, (level, sibling)| {
+                if (self.pos >> level) & 1 == 0 {
+                    mmr_branch(level as u8 + 1, node, *sibling)
+                } else {
+                    mmr_branch(level as u8 + 1, *sibling, node)
+                }
+            });
+        (self.peaks[index] == peak).then(|| bag_peaks(self.width, &self.peaks))
+    }
+
+    /// Verifies that the `leaf` is included into the range with the given
+    /// `root` at the proof position.
+    pub fn verify(&self, leaf: MerkleHash, root: MerkleHash) -> bool {
+        self.mmr_root(leaf) == Some(root)
+    }
+}
+
+/// Append-only log of commit-encodable items, committing to each of the items
+/// with a [`MerkleMountainRange`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct CommitLog<T: CommitEncode<CommitmentId = MerkleHash>> {
+    mmr: MerkleMountainRange,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: CommitEncode<CommitmentId = MerkleHash>> Default for CommitLog<T> {
+    fn default() -> Self {
+        Self {
+            mmr: default!(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: CommitEncode<CommitmentId = MerkleHash>> CommitLog<T> {
+    /// Constructs empty log.
+    pub fn new() -> Self { Self::default() }
+
+    /// Number of items in the log.
+    pub fn len(&self) -> u64 { self.mmr.len() }
+
+    /// Detects whether the log has no items.
+    pub fn is_empty(&self) -> bool { self.mmr.is_empty() }
+
+    /// Returns the underlying merkle mountain range.
+    pub fn as_mmr(&self) -> &MerkleMountainRange { &self.mmr }
+
+    /// Current root of the log.
+    pub fn root(&self) -> MerkleHash { self.mmr.root() }
+
+    /// Appends `item` to the log, returning the proof of its inclusion into
+    /// the updated log.
+    pub fn push(&mut self, item: &T) -> AppendProof { self.mmr.push(item.commit_id()) }
+
+    /// Produces proof of inclusion of the item at `pos` into the current state
+    /// of the log.
+    pub fn prove(&self, pos: u64) -> Option<AppendProof> { self.mmr.prove(pos) }
+
+    /// Updates `proof` produced for an earlier state of the log, such that it
+    /// verifies against the current root (see
+    /// [`MerkleMountainRange::update_proof`]).
+    pub fn update_proof(&self, proof: &AppendProof) -> Option<AppendProof> {
+        self.mmr.update_proof(proof)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mpc::Leaf;
+
+    #[test]
+    fn mmr() {
+        let mut mmr = MerkleMountainRange::new();
+        assert!(mmr.is_empty());
+        assert!(mmr.peaks().is_empty());
+        for no in 0..11u32 {
+            let leaf = Leaf::entropy(0xdead, no).commit_id();
+            let proof = mmr.push(leaf);
+            assert!(proof.verify(leaf, mmr.root()));
+        }
+        assert_eq!(mmr.len(), 11);
+        assert_eq!(mmr.peaks().len(), 3);
+        for no in 0..11u32 {
+            let leaf = Leaf::entropy(0xdead, no).commit_id();
+            let proof = mmr.prove(no as u64).unwrap();
+            assert!(proof.verify(leaf, mmr.root()));
+            assert!(!proof.verify(Leaf::entropy(0xbeef, no).commit_id(), mmr.root()));
+        }
+        assert!(mmr.prove(11).is_none());
+    }
+
+    #[test]
+    fn commit_log() {
+        let events = [Leaf::entropy(1, 0), Leaf::entropy(2, 0), Leaf::entropy(3, 0)];
+        let mut log = CommitLog::new();
+        let mut appended = vec![];
+        for event in &events {
+            let proof = log.push(event);
+            appended.push((proof, log.root()));
+        }
+        assert_eq!(log.len(), 3);
+
+        let root = log.root();
+        for (pos, event) in events.iter().enumerate() {
+            let (proof, root_then) = &appended[pos];
+            assert!(proof.verify(event.commit_id(), *root_then));
+            assert!(log
+                .prove(pos as u64)
+                .unwrap()
+                .verify(event.commit_id(), root));
+        }
+        assert!(!log.prove(0).unwrap().verify(events[1].commit_id(), root));
+
+        // Proofs returned on append are bound to the root at that moment and
+        // must be updated to verify against a later root
+        for (pos, event) in events.iter().enumerate() {
+            let (proof, _) = &appended[pos];
+            let updated = log.update_proof(proof).unwrap();
+            assert!(updated.verify(event.commit_id(), root));
+        }
+        assert!(!appended[0].0.verify(events[0].commit_id(), root));
+
+        let mut other = CommitLog::new();
+        other.push(&events[1]);
+        other.push(&events[1]);
+        other.push(&events[2]);
+        assert_eq!(other.update_proof(&appended[0].0), None);
+        assert_eq!(other.update_proof(&appended[2].0), None);
+    }
+
+    #[test]
+    fn update_proof() {
+        let mut mmr = MerkleMountainRange::new();
+        let mut proofs = vec![];
+        for no in 0..5u32 {
+            proofs.push(mmr.push(Leaf::entropy(0xdead, no).commit_id()));
+        }
+        let early = mmr.clone();
+        for no in 5..23u32 {
+            proofs.push(mmr.push(Leaf::entropy(0xdead, no).commit_id()));
+        }
+        for (no, proof) in proofs.iter().enumerate() {
+            let leaf = Leaf::entropy(0xdead, no as u32).commit_id();
+            let updated = mmr.update_proof(proof).unwrap();
+            assert!(updated.verify(leaf, mmr.root()));
+            assert_eq!(updated, mmr.prove(no as u64).unwrap());
+        }
+        // A later proof can't be updated to an earlier state
+        assert_eq!(early.update_proof(&proofs[10]), None);
+
+        // Malformed proofs are rejected
+        let mut malformed = proofs[3].clone();
+        malformed.pos = 7;
+        assert_eq!(mmr.update_proof(&malformed), None);
+        malformed.pos = malformed.width;
+        assert_eq!(mmr.update_proof(&malformed), None);
+        let mut malformed = mmr.prove(3).unwrap();
+        malformed.width = mmr.len() + 1;
+        assert_eq!(mmr.update_proof(&malformed), None);
+        let mut malformed = proofs[3].clone();
+        malformed.peaks.pop();
+        assert_eq!(mmr.update_proof(&malformed), None);
+    }
+}
@@ -24,9 +24,12 @@
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, BTreeSet};
 
-use amplify::confinement::{Confined, NonEmptyVec, U32 as U32MAX};
+use amplify::confinement::{Confined, LargeVec, NonEmptyVec, U32 as U32MAX};
 use amplify::num::u5;
-use strict_encoding::{StrictDeserialize, StrictDumb, StrictEncode, StrictSerialize};
+use strict_encoding::{
+    DecodeError, ReadStruct, StrictDecode, StrictDeserialize, StrictDumb, StrictEncode,
+    StrictSerialize, TypedRead,
+};
 
 use crate::id::CommitId;
 use crate::merkle::{MerkleBuoy, MerkleHash};
@@ -67,6 +70,10 @@ pub enum MergeError {
         base_root: Commitment,
         merged_root: Commitment,
     },
+
+    /// attempt to merge LNPBP-4 blocks constructed with different methods
+    /// (base {base}, merged-in {merged}).
+    MethodMismatch { base: Method, merged: Method },
 }
 
 /// LNPBP-4 Merkle tree node.
@@ -424,6 +431,22 @@ impl MerkleBlock {
         Ok(count)
     }
 
+    /// Minimizes the block by aggregating all sibling concealed nodes into
+    /// their parent node hashes, keeping all revealed protocol leafs. Also
+    /// removes information about the entropy value used.
+    ///
+    /// The minimized block has the same commitment and produces the same
+    /// proofs for the revealed protocols as the original one.
+    ///
+    /// # Returns
+    ///
+    /// Number of aggregated nodes.
+    pub fn minimize(&mut self) -> usize {
+        let protocols = self.known_protocol_ids().collect::<Vec<_>>();
+        self.conceal_except(protocols)
+            .expect("protocol ids are taken from the block itself")
+    }
+
     /// Merges information from the given `proof` to the merkle block, revealing
     /// path related to te `commitment` to the message under the given
     /// `protocol_id`.
@@ -440,6 +463,13 @@ impl MerkleBlock {
     /// Merges two merkle blocks together, joining revealed information from
     /// each one of them.
     pub fn merge_reveal(&mut self, other: MerkleBlock) -> Result<u16, MergeError> {
+        if self.method != other.method {
+            return Err(MergeError::MethodMismatch {
+                base: self.method,
+                merged: other.method,
+            });
+        }
+
         let orig = self.clone();
         let base_root = self.commit_id();
         let merged_root = other.commit_id();
@@ -625,6 +655,110 @@ Changed commitment id: {}",
         .expect("same collection size")
     }
 
+    /// Constructs single proof of inclusion of commitments under all of the
+    /// given `protocols`, which should be revealed together.
+    pub fn to_multi_proof(
+        &self,
+        protocols: impl AsRef<[ProtocolId]>,
+    ) -> Result<MultiMerkleProof, LeafNotKnown> {
+        let mut block = self.clone();
+        block.conceal_except(protocols)?;
+        let concealed = block
+            .cross_section
+            .into_iter()
+            .filter(|node| !node.is_leaf());
+        Ok(MultiMerkleProof {
+            method: block.method,
+            depth: block.depth,
+            cofactor: block.cofactor,
+            concealed: LargeVec::try_from_iter(concealed).expect("same collection size"),
+        })
+    }
+
+    /// Constructs proof that no commitment under the given `protocol_id` is
+    /// present in the tree, by revealing the leaf at the position of the
+    /// protocol, which holds either a commitment under a different protocol or
+    /// an entropy placeholder.
+    ///
+    /// # Error
+    ///
+    /// If the leaf at the protocol position is concealed (and the block
+    /// entropy, if it is an entropy placeholder, is not known), or if it holds
+    /// the commitment under the `protocol_id` itself, errors with
+    /// [`LeafNotKnown`] error.
+    pub fn prove_absence(&self, protocol_id: ProtocolId) -> Result<AbsenceProof, LeafNotKnown> {
+        let pos = self.protocol_id_pos(protocol_id);
+        let index = self.leaf_index(pos).ok_or(LeafNotKnown(protocol_id))?;
+
+        let leaf = match self.cross_section[index] {
+            TreeNode::CommitmentLeaf {
+                protocol_id: protocol,
+                message,
+            } if protocol != protocol_id => Leaf::inhabited(protocol, message),
+            TreeNode::ConcealedNode { hash, .. } => self
+                .entropy
+                .map(|entropy| Leaf::entropy(entropy, pos))
+                .filter(|leaf| leaf.commit_id() == hash)
+                .ok_or(LeafNotKnown(protocol_id))?,
+            TreeNode::CommitmentLeaf { .. } => return Err(LeafNotKnown(protocol_id)),
+        };
+
+        // The path consists of the sibling nodes only, thus it doesn't depend on
+        // the leaf value, and we can use a placeholder leaf for its construction.
+        let mut block = self.clone();
+        block.cross_section[index] = TreeNode::CommitmentLeaf {
+            protocol_id,
+            message: Message::default(),
+        };
+        let proof = block.into_merkle_proof(protocol_id)?;
+        Ok(AbsenceProof {
+            method: proof.method,
+            pos,
+            cofactor: proof.cofactor,
+            leaf,
+            path: proof.path,
+        })
+    }
+
+    /// Returns protocol and message of the leaf at the given position within
+    /// the tree leaves, if the leaf is revealed. Returns `None` for the
+    /// positions occupied by concealed leaves and entropy placeholders, and
+    /// for the positions outside of the tree width.
+    ///
+    /// Inverse of [`MerkleBlock::protocol_id_pos`] for the revealed
+    /// protocols.
+    pub fn leaf_at(&self, pos: u32) -> Option<(ProtocolId, Message)> {
+        match self.cross_section[self.leaf_index(pos)?] {
+            TreeNode::CommitmentLeaf {
+                protocol_id,
+                message,
+            } => Some((protocol_id, message)),
+            TreeNode::ConcealedNode { .. } => None,
+        }
+    }
+
+    /// Finds the index of the cross-section node which is a leaf (either
+    /// revealed or concealed) at the given position within the tree leaves.
+    fn leaf_index(&self, pos: u32) -> Option<usize> {
+        let mut offset = 0u32;
+        for (no, node) in self.cross_section.iter().enumerate() {
+            let width = match node {
+                TreeNode::ConcealedNode { depth, .. } => {
+                    1u32 << (self.depth.to_u8() - depth.to_u8())
+                }
+                TreeNode::CommitmentLeaf { .. } => 1,
+            };
+            if offset == pos && width == 1 {
+                return Some(no);
+            }
+            offset += width;
+            if offset > pos {
+                break;
+            }
+        }
+        None
+    }
+
     pub fn into_known_proofs(self) -> impl Iterator<Item = (ProtocolId, MerkleProof)> {
         self.known_protocol_ids()
             .collect::<Vec<_>>()
@@ -638,6 +772,43 @@ Changed commitment id: {}",
     }
 }
 
+#[cfg(feature = "debug-graph")]
+impl MerkleBlock {
+    /// Produces Graphviz DOT representation of the block for debugging
+    /// purposes.
+    ///
+    /// Each cross-section entry is represented by a separate node connected to
+    /// the root node labeled with the block commitment; leaves are labeled with
+    /// the prefix of their protocol id, and concealed nodes are drawn dashed.
+    pub fn to_dot(&self) -> String {
+        use amplify::hex::ToHex;
+
+        let mut dot = s!("digraph mpc {\n");
+        dot.push_str(&format!(
+            "    label=\"method={} depth={} cofactor={}\";\n",
+            self.method, self.depth, self.cofactor
+        ));
+        dot.push_str(&format!(
+            "    root [label=\"{}\", shape=doubleoctagon];\n",
+            &self.commit_id().to_hex()[..8]
+        ));
+        for (no, node) in self.cross_section.iter().enumerate() {
+            let label = match node {
+                TreeNode::ConcealedNode { depth, hash } => {
+                    format!("label=\"{}\\ndepth {depth}\", style=dashed", &hash.to_hex()[..8])
+                }
+                TreeNode::CommitmentLeaf { protocol_id, .. } => {
+                    format!("label=\"{}\", shape=box", &protocol_id.to_hex()[..8])
+                }
+            };
+            dot.push_str(&format!("    n{no} [{label}];\n"));
+            dot.push_str(&format!("    root -> n{no};\n"));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
 impl Conceal for MerkleBlock {
     type Concealed = MerkleConcealed;
 
@@ -664,10 +835,18 @@ impl Conceal for MerkleBlock {
 }
 
 /// A proof of the merkle commitment.
+///
+/// The depth of the tree is defined by the length of the merkle path, thus the
+/// proof is self-contained and its decoding fails if the leaf position doesn't
+/// fit into the tree of that depth.
 #[derive(Getters, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
-#[derive(StrictType, StrictEncode, StrictDecode)]
+#[derive(StrictType, StrictEncode)]
 #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "UncheckedMerkleProof")
+)]
 pub struct MerkleProof {
     /// Method used to construct MPC proof (hash function, merklization).
     #[getter(as_copy)]
@@ -689,6 +868,41 @@ pub struct MerkleProof {
     path: Confined<Vec<MerkleHash>, 0, 32>,
 }
 
+impl StrictDecode for MerkleProof {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        Self::strict_decode_with(reader, false)
+    }
+}
+
+/// Serde representation of [`MerkleProof`], which is validated on conversion
+/// in the same way as the strict decoding.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct UncheckedMerkleProof {
+    method: Method,
+    pos: u32,
+    cofactor: u16,
+    path: Confined<Vec<MerkleHash>, 0, 32>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedMerkleProof> for MerkleProof {
+    type Error = DecodeError;
+
+    fn try_from(proof: UncheckedMerkleProof) -> Result<Self, Self::Error> {
+        MerkleProof {
+            method: proof.method,
+            pos: proof.pos,
+            cofactor: proof.cofactor,
+            path: proof.path,
+        }
+        .checked()
+    }
+}
+
+impl StrictSerialize for MerkleProof {}
+impl StrictDeserialize for MerkleProof {}
+
 impl Proof for MerkleProof {
     fn matches(&self, other: &Self) -> bool {
         self.cofactor == other.cofactor && self.merkle_root() == other.merkle_root()
@@ -696,6 +910,42 @@ impl Proof for MerkleProof {
 }
 
 impl MerkleProof {
+    /// Decodes the proof, checking that the leaf position fits into the tree
+    /// of the depth defined by the merkle path.
+    ///
+    /// If `legacy` flag is set, the check is skipped, allowing to decode proofs
+    /// produced before the check was introduced. Such proofs must not be
+    /// trusted without verification against a known commitment.
+    pub fn strict_decode_with(
+        reader: &mut impl TypedRead,
+        legacy: bool,
+    ) -> Result<Self, DecodeError> {
+        let proof = reader.read_struct(|r| {
+            Ok(MerkleProof {
+                method: r.read_field(fname!("method"))?,
+                pos: r.read_field(fname!("pos"))?,
+                cofactor: r.read_field(fname!("cofactor"))?,
+                path: r.read_field(fname!("path"))?,
+            })
+        })?;
+        if legacy {
+            return Ok(proof);
+        }
+        proof.checked()
+    }
+
+    fn checked(self) -> Result<Self, DecodeError> {
+        if self.pos >= self.width_limit() {
+            return Err(DecodeError::DataIntegrityError(format!(
+                "merkle proof leaf position {} exceeds the width {} of the tree with depth {}",
+                self.pos,
+                self.width_limit(),
+                self.depth()
+            )));
+        }
+        Ok(self)
+    }
+
     /// Computes the depth of the merkle tree.
     pub fn depth(&self) -> u5 { u5::with(self.path.len() as u8) }
 
@@ -732,12 +982,205 @@ impl MerkleProof {
     }
 }
 
+/// Proof that a commitment under some protocol is absent from an LNPBP-4
+/// Merkle tree, revealing the leaf at the position of the protocol in the tree
+/// (see [`MerkleBlock::prove_absence`]).
+#[derive(Getters, Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+pub struct AbsenceProof {
+    /// Method used to construct MPC proof (hash function, merklization).
+    #[getter(as_copy)]
+    method: Method,
+
+    /// Position of the protocol in the tree.
+    #[getter(as_copy)]
+    pos: u32,
+
+    /// Cofactor used by the Merkle tree.
+    #[getter(as_copy)]
+    cofactor: u16,
+
+    /// Leaf at the protocol position.
+    #[getter(as_copy)]
+    leaf: Leaf,
+
+    /// Merkle proof path consisting of node hashing partners.
+    #[getter(skip)]
+    path: Confined<Vec<MerkleHash>, 0, 32>,
+}
+
+impl StrictSerialize for AbsenceProof {}
+impl StrictDeserialize for AbsenceProof {}
+
+impl AbsenceProof {
+    /// Computes the depth of the merkle tree.
+    pub fn depth(&self) -> u5 { u5::with(self.path.len() as u8) }
+
+    /// Verifies that no commitment under `protocol_id` is present in the tree
+    /// with the `expected` commitment.
+    pub fn verify(&self, protocol_id: ProtocolId, expected: Commitment) -> bool {
+        if protocol_id_pos(protocol_id, self.cofactor, self.depth()) != self.pos {
+            return false;
+        }
+        match self.leaf {
+            Leaf::Inhabited { protocol, .. } if protocol == protocol_id => return false,
+            Leaf::Entropy { pos, .. } if pos != self.pos => return false,
+            _ => {}
+        }
+
+        let proof = MerkleProof {
+            method: self.method,
+            pos: self.pos,
+            cofactor: self.cofactor,
+            path: self.path.clone(),
+        };
+        let Ok(mut block) = MerkleBlock::with(&proof, protocol_id, Message::default()) else {
+            return false;
+        };
+        for node in &mut block.cross_section {
+            if node.is_leaf() {
+                *node = TreeNode::ConcealedNode {
+                    depth: block.depth,
+                    hash: self.leaf.commit_id(),
+                };
+            }
+        }
+        block.commit_id() == expected
+    }
+}
+
+/// Proof of inclusion of commitments under multiple protocols into a single
+/// LNPBP-4 Merkle tree.
+///
+/// Unlike a set of [`MerkleProof`]s, stores the concealed part of the tree
+/// cross-section shared by all of the protocols just once, not including the
+/// revealed leaves, which are provided during the verification.
+#[derive(Getters, Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultiMerkleProof {
+    /// Method used to construct MPC proof (hash function, merklization).
+    #[getter(as_copy)]
+    method: Method,
+
+    /// Tree depth (up to 16).
+    #[getter(as_copy)]
+    depth: u5,
+
+    /// Cofactor used by the Merkle tree.
+    #[getter(as_copy)]
+    cofactor: u16,
+
+    /// Concealed nodes of the tree cross-section, ordered by their position.
+    #[getter(skip)]
+    concealed: LargeVec<TreeNode>,
+}
+
+impl StrictSerialize for MultiMerkleProof {}
+impl StrictDeserialize for MultiMerkleProof {}
+
+impl MultiMerkleProof {
+    /// Computes the maximum width of the merkle tree.
+    pub fn width_limit(&self) -> u32 { 2u32.pow(self.depth.to_u8() as u32) }
+
+    /// Reconstructs merkle block revealing commitments under the provided
+    /// `protocols`, or returns `None` if the protocols can't be placed into the
+    /// tree cross-section of the proof.
+    pub fn to_merkle_block(&self, protocols: &[(ProtocolId, Message)]) -> Option<MerkleBlock> {
+        let mut leaves = protocols
+            .iter()
+            .map(|(protocol_id, message)| {
+                (protocol_id_pos(*protocol_id, self.cofactor, self.depth), *protocol_id, *message)
+            })
+            .collect::<Vec<_>>();
+        leaves.sort_by_key(|(pos, ..)| *pos);
+        let mut leaves = leaves.into_iter().peekable();
+
+        let mut cross_section = Vec::with_capacity(self.concealed.len() + protocols.len());
+        let mut pos = 0u32;
+        let mut place_leaves = |pos: &mut u32, cross_section: &mut Vec<TreeNode>| {
+            while let Some((_, protocol_id, message)) =
+                leaves.next_if(|(leaf_pos, ..)| leaf_pos == pos)
+            {
+                cross_section.push(TreeNode::CommitmentLeaf {
+                    protocol_id,
+                    message,
+                });
+                *pos += 1;
+            }
+        };
+        for node in self.concealed.iter().copied() {
+            place_leaves(&mut pos, &mut cross_section);
+            let depth = node.depth()?;
+            if depth > self.depth {
+                return None;
+            }
+            // Concealed node must cover a whole subtree of the tree
+            let width = 1u32 << (self.depth.to_u8() - depth.to_u8());
+            if pos % width != 0 {
+                return None;
+            }
+            pos = pos.checked_add(width)?;
+            cross_section.push(node);
+        }
+        place_leaves(&mut pos, &mut cross_section);
+        if leaves.next().is_some() || pos != self.width_limit() {
+            return None;
+        }
+
+        Some(MerkleBlock {
+            method: self.method,
+            depth: self.depth,
+            cofactor: self.cofactor,
+            cross_section: NonEmptyVec::try_from(cross_section).ok()?,
+            entropy: None,
+        })
+    }
+
+    /// Verifies that commitments under all of the `protocols` are included
+    /// into the tree with the `expected` commitment.
+    pub fn verify(&self, protocols: &[(ProtocolId, Message)], expected: Commitment) -> bool {
+        self.to_merkle_block(protocols)
+            .map(|block| block.commit_id() == expected)
+            .unwrap_or_default()
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use strict_encoding::{DeserializeError, StrictReader};
+
     use super::*;
     use crate::mpc::tree::test_helpers::{
         make_det_messages, make_random_messages, make_random_tree,
     };
+    use crate::mpc::MultiSource;
+    use crate::TryCommitVerify;
+
+    #[test]
+    #[cfg(feature = "debug-graph")]
+    fn to_dot() {
+        use amplify::hex::ToHex;
+
+        let msgs = make_random_messages(9);
+        let tree = make_random_tree(&msgs);
+        let mut block = MerkleBlock::from(&tree);
+        let protocol_id = *msgs.keys().next().unwrap();
+        block.conceal_except([protocol_id]).unwrap();
+
+        let dot = block.to_dot();
+        assert!(dot.starts_with("digraph mpc {"));
+        assert_eq!(dot.matches(" -> ").count(), block.cross_section.len());
+        assert_eq!(dot.matches("shape=box").count(), 1);
+        assert_eq!(dot.matches("style=dashed").count(), block.cross_section.len() - 1);
+        assert!(dot.contains(&protocol_id.to_hex()[..8]));
+
+        let dot = tree.to_dot();
+        assert_eq!(dot.matches("shape=box").count(), msgs.len());
+        assert_eq!(dot.matches(" -> ").count(), tree.width_limit() as usize);
+    }
 
     #[test]
     fn entropy() {
@@ -795,6 +1238,186 @@ mod test {
         }
     }
 
+    #[test]
+    fn proof_decode() {
+        let msgs = make_random_messages(5);
+        let tree = make_random_tree(&msgs);
+        let block = MerkleBlock::from(&tree);
+        let pid = *msgs.keys().next().unwrap();
+        let proof = block.to_merkle_proof(pid).unwrap();
+
+        let data = proof.to_strict_serialized::<U32MAX>().unwrap();
+        assert_eq!(MerkleProof::from_strict_serialized::<U32MAX>(data).unwrap(), proof);
+
+        let mut invalid = proof.clone();
+        invalid.pos = proof.width_limit();
+        let data = invalid.to_strict_serialized::<U32MAX>().unwrap();
+        assert!(matches!(
+            MerkleProof::from_strict_serialized::<U32MAX>(data.clone()),
+            Err(DeserializeError::Decode(DecodeError::DataIntegrityError(_)))
+        ));
+
+        let mut reader = StrictReader::in_memory::<U32MAX>(data.release());
+        assert_eq!(MerkleProof::strict_decode_with(&mut reader, true).unwrap(), invalid);
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    fn proof_serde() {
+        let msgs = make_random_messages(5);
+        let tree = make_random_tree(&msgs);
+        let block = MerkleBlock::from(&tree);
+        let pid = *msgs.keys().next().unwrap();
+        let proof = block.to_merkle_proof(pid).unwrap();
+
+        let json = serde_json::to_string(&proof).unwrap();
+        assert_eq!(serde_json::from_str::<MerkleProof>(&json).unwrap(), proof);
+
+        let mut invalid = proof.clone();
+        invalid.pos = proof.width_limit();
+        let json = serde_json::to_string(&invalid).unwrap();
+        assert!(serde_json::from_str::<MerkleProof>(&json).is_err());
+    }
+
+    #[test]
+    fn multi_proof() {
+        let msgs = make_random_messages(3);
+        let tree = make_random_tree(&msgs);
+        let block = MerkleBlock::from(&tree);
+        let mut iter = msgs.iter().map(|(p, m)| (*p, *m));
+        let (first, second, third) =
+            (iter.next().unwrap(), iter.next().unwrap(), iter.next().unwrap());
+
+        let proof = block.to_multi_proof([first.0, second.0]).unwrap();
+        assert!(proof.verify(&[first, second], tree.commit_id()));
+        assert!(proof.verify(&[second, first], tree.commit_id()));
+        assert!(!proof.verify(&[first], tree.commit_id()));
+        assert!(!proof.verify(&[first, third], tree.commit_id()));
+        assert!(!proof.verify(&[first, second, third], tree.commit_id()));
+        assert!(!proof.verify(&[first, (second.0, third.1)], tree.commit_id()));
+
+        let all = block.to_multi_proof([first.0, second.0, third.0]).unwrap();
+        assert!(all.verify(&[first, second, third], tree.commit_id()));
+
+        let proof = MultiMerkleProof::from_strict_serialized::<{ usize::MAX }>(
+            proof.to_strict_serialized::<{ usize::MAX }>().unwrap(),
+        )
+        .unwrap();
+        assert!(proof.verify(&[first, second], tree.commit_id()));
+    }
+
+    #[test]
+    fn leaf_at() {
+        let msgs = make_random_messages(5);
+        let tree = make_random_tree(&msgs);
+        let mut block = MerkleBlock::from(&tree);
+        for (protocol_id, message) in &msgs {
+            let pos = block.protocol_id_pos(*protocol_id);
+            assert_eq!(block.leaf_at(pos), Some((*protocol_id, *message)));
+        }
+        let occupied = msgs
+            .keys()
+            .map(|protocol_id| block.protocol_id_pos(*protocol_id))
+            .collect::<BTreeSet<_>>();
+        let free = (0..block.width_limit())
+            .find(|pos| !occupied.contains(pos))
+            .unwrap();
+        assert_eq!(block.leaf_at(free), None);
+        assert_eq!(block.leaf_at(block.width_limit()), None);
+
+        let (revealed, _) = msgs.first_key_value().unwrap();
+        block.conceal_other(*revealed).unwrap();
+        for (protocol_id, message) in &msgs {
+            let pos = block.protocol_id_pos(*protocol_id);
+            let expected = (protocol_id == revealed).then_some((*protocol_id, *message));
+            assert_eq!(block.leaf_at(pos), expected);
+        }
+    }
+
+    #[test]
+    fn absence_proof() {
+        let msgs = make_random_messages(3);
+        let src = MultiSource {
+            method: Method::Sha256t,
+            min_depth: u5::with(3),
+            messages: Confined::try_from_iter(msgs.iter().map(|(a, b)| (*a, *b))).unwrap(),
+            static_entropy: Some(0xdeadbeef),
+        };
+        let tree = MerkleTree::try_commit(&src).unwrap();
+        let block = MerkleBlock::from(&tree);
+
+        let present = *msgs.keys().next().unwrap();
+        assert_eq!(block.prove_absence(present), Err(LeafNotKnown(present)));
+
+        let (mut entropy_slot, mut occupied_slot) = (None, None);
+        for no in 0u8..=255 {
+            let protocol_id = ProtocolId::from([no; 32]);
+            if msgs.contains_key(&protocol_id) {
+                continue;
+            }
+            match tree.map.get(&block.protocol_id_pos(protocol_id)) {
+                None => entropy_slot = entropy_slot.or(Some(protocol_id)),
+                Some(_) => occupied_slot = occupied_slot.or(Some(protocol_id)),
+            }
+        }
+
+        for protocol_id in [entropy_slot.unwrap(), occupied_slot.unwrap()] {
+            let proof = block.prove_absence(protocol_id).unwrap();
+            assert!(proof.verify(protocol_id, tree.commit_id()));
+            assert!(!proof.verify(present, tree.commit_id()));
+            assert!(!proof.verify(protocol_id, make_random_tree(&msgs).commit_id()));
+        }
+
+        let entropy_slot = entropy_slot.unwrap();
+        let proof = block.prove_absence(entropy_slot).unwrap();
+        assert!(matches!(proof.leaf(), Leaf::Entropy { .. }));
+        let proof = AbsenceProof::from_strict_serialized::<U32MAX>(
+            proof.to_strict_serialized::<U32MAX>().unwrap(),
+        )
+        .unwrap();
+        assert!(proof.verify(entropy_slot, tree.commit_id()));
+
+        let mut concealed = block.clone();
+        concealed.conceal_other(present).unwrap();
+        assert_eq!(concealed.prove_absence(entropy_slot), Err(LeafNotKnown(entropy_slot)));
+    }
+
+    #[test]
+    fn minimize() {
+        for size in 1..9 {
+            let msgs = make_random_messages(size);
+            let tree = make_random_tree(&msgs);
+            let block = MerkleBlock::from(&tree);
+
+            let mut minimized = block.clone();
+            minimized.minimize();
+            assert_eq!(minimized.entropy, None);
+            assert!(minimized.cross_section.len() <= block.cross_section.len());
+            assert_eq!(minimized.commit_id(), block.commit_id());
+            assert_eq!(minimized.to_known_message_map(), block.to_known_message_map());
+            for pid in msgs.keys() {
+                assert_eq!(
+                    minimized.to_merkle_proof(*pid).unwrap(),
+                    block.to_merkle_proof(*pid).unwrap()
+                );
+            }
+            assert_eq!(minimized.clone().minimize(), 0);
+        }
+    }
+
+    // `Method` has a single variant, so `MergeError::MethodMismatch` can't be
+    // produced by a test until another method is added.
+    #[test]
+    fn method_getters() {
+        let msgs = make_random_messages(3);
+        let tree = make_random_tree(&msgs);
+        let block = MerkleBlock::from(&tree);
+        let proof = block.to_merkle_proof(*msgs.keys().next().unwrap()).unwrap();
+        assert_eq!(tree.method(), Method::Sha256t);
+        assert_eq!(block.method(), tree.method());
+        assert_eq!(proof.method(), tree.method());
+    }
+
     #[test]
     fn merge_reveal() {
         for size in 2..9 {
@@ -807,7 +1430,7 @@ mod test {
                 .map(|pid| mpc_block.to_merkle_proof(*pid).unwrap())
                 .collect::<Vec<_>>();
 
-            let mut iter = proofs.iter().zip(msgs.into_iter());
+            let mut iter = proofs.iter().zip(msgs);
             let (proof, (pid, msg)) = iter.next().unwrap();
             let mut merged_block = MerkleBlock::with(proof, pid, msg).unwrap();
             for (proof, (pid, msg)) in iter {
@@ -31,7 +31,8 @@ pub use atoms::{
     Commitment, Leaf, Message, MessageMap, Method, MultiSource, ProtocolId, MPC_MINIMAL_DEPTH,
 };
 pub use block::{
-    InvalidProof, LeafNotKnown, MergeError, MerkleBlock, MerkleConcealed, MerkleProof,
+    AbsenceProof, InvalidProof, LeafNotKnown, MergeError, MerkleBlock, MerkleConcealed,
+    MerkleProof, MultiMerkleProof,
 };
 pub use tree::{Error, MerkleTree};
 
@@ -115,57 +115,43 @@ mod commit {
         CantFitInMaxSlots(usize),
     }
 
-    /// # Panics
-    ///
-    /// Panics if the crate is compiled without `rand` feature enabled and the
-    /// MultiSource doesn't contain a static entropy.
-    impl TryCommitVerify<MultiSource, UntaggedProtocol> for MerkleTree {
-        type Error = Error;
-
-        fn try_commit(source: &MultiSource) -> Result<Self, Error> {
-            #[cfg(feature = "rand")]
-            use rand::{thread_rng, RngCore};
-
-            let msg_count = source.messages.len();
-
-            if source.min_depth == u5::ZERO && source.messages.is_empty() {
+    impl MultiSource {
+        /// Computes the minimal depth of the LNPBP-4 tree (not less than
+        /// [`MultiSource::min_depth`]) which is able to fit all messages of the
+        /// source without collisions of their protocol positions.
+        ///
+        /// The depth is the same as the one of the tree which will be produced
+        /// from this source by [`MerkleTree::try_commit`], however the
+        /// computation doesn't require entropy and doesn't construct the tree.
+        pub fn minimal_depth(&self) -> Result<u5, Error> { self.place().map(|(depth, ..)| depth) }
+
+        /// Finds the minimal tree depth and a cofactor for the depth allowing
+        /// to place all messages without collisions.
+        fn place(&self) -> Result<(u5, u16, OrderedMap), Error> {
+            let msg_count = self.messages.len();
+
+            if self.min_depth == u5::ZERO && self.messages.is_empty() {
                 return Err(Error::Empty);
             }
             if msg_count > 2usize.pow(u5::MAX.to_u8() as u32) {
                 return Err(Error::TooManyMessages(msg_count));
             }
 
-            #[cfg(feature = "rand")]
-            let entropy = source
-                .static_entropy
-                .unwrap_or_else(|| thread_rng().next_u64());
-            #[cfg(not(feature = "rand"))]
-            let entropy = source.static_entropy.expect(
-                "use must use `rand` feature for crate commit_verify if you do not provide with a \
-                 static entropy information in `MultiSource`",
-            );
-
             let mut map = BTreeMap::<u32, (ProtocolId, Message)>::new();
 
-            let mut depth = source.min_depth;
+            let mut depth = self.min_depth;
             let mut prev_width = 1u32;
             loop {
                 let width_limit = 2u32.pow(depth.to_u8() as u32);
                 if width_limit as usize >= msg_count {
                     for cofactor in 0..=(prev_width.min(COFACTOR_ATTEMPTS as u32) as u16) {
                         map.clear();
-                        if source.messages.iter().all(|(protocol, message)| {
+                        if self.messages.iter().all(|(protocol, message)| {
                             let pos = protocol_id_pos(*protocol, cofactor, depth);
                             map.insert(pos, (*protocol, *message)).is_none()
                         }) {
-                            return Ok(MerkleTree {
-                                method: source.method,
-                                depth,
-                                entropy,
-                                cofactor,
-                                messages: source.messages.clone(),
-                                map: Confined::try_from(map).expect("MultiSource type guarantees"),
-                            });
+                            let map = Confined::try_from(map).expect("MultiSource type guarantees");
+                            return Ok((depth, cofactor, map));
                         }
                     }
                 }
@@ -177,6 +163,40 @@ mod commit {
             }
         }
     }
+
+    /// # Panics
+    ///
+    /// Panics if the crate is compiled without `rand` feature enabled and the
+    /// MultiSource doesn't contain a static entropy.
+    impl TryCommitVerify<MultiSource, UntaggedProtocol> for MerkleTree {
+        type Error = Error;
+
+        fn try_commit(source: &MultiSource) -> Result<Self, Error> {
+            #[cfg(feature = "rand")]
+            use rand::{thread_rng, RngCore};
+
+            let (depth, cofactor, map) = source.place()?;
+
+            #[cfg(feature = "rand")]
+            let entropy = source
+                .static_entropy
+                .unwrap_or_else(|| thread_rng().next_u64());
+            #[cfg(not(feature = "rand"))]
+            let entropy = source.static_entropy.expect(
+                "use must use `rand` feature for crate commit_verify if you do not provide with a \
+                 static entropy information in `MultiSource`",
+            );
+
+            Ok(MerkleTree {
+                method: source.method,
+                depth,
+                entropy,
+                cofactor,
+                messages: source.messages.clone(),
+                map,
+            })
+        }
+    }
 }
 
 pub(super) fn protocol_id_pos(protocol_id: ProtocolId, cofactor: u16, depth: u5) -> u32 {
@@ -201,12 +221,20 @@ impl MerkleTree {
     /// cofactor`.
     pub fn factored_width(&self) -> u32 { self.width_limit() - self.cofactor as u32 }
 
+    /// Returns method used to construct the tree.
+    pub fn method(&self) -> Method { self.method }
+
     pub fn depth(&self) -> u5 { self.depth }
 
     pub fn cofactor(&self) -> u16 { self.cofactor }
 
     pub fn entropy(&self) -> u64 { self.entropy }
 
+    /// Produces Graphviz DOT representation of the tree for debugging
+    /// purposes (see [`MerkleBlock::to_dot`]).
+    #[cfg(feature = "debug-graph")]
+    pub fn to_dot(&self) -> String { MerkleBlock::from(self).to_dot() }
+
     pub fn into_proofs(self) -> impl Iterator<Item = (ProtocolId, MerkleProof)> {
         let block = MerkleBlock::from(self);
         block.into_known_proofs()
@@ -257,7 +285,7 @@ pub(crate) mod test_helpers {
             method: Method::Sha256t,
             min_depth: u5::ZERO,
             messages: Confined::try_from_iter(msgs.iter().map(|(a, b)| (*a, *b))).unwrap(),
-            static_entropy: None,
+            static_entropy: Some(random()),
         };
         MerkleTree::try_commit(&src).unwrap()
     }
@@ -267,14 +295,16 @@ pub(crate) mod test_helpers {
 mod test {
     use std::collections::BTreeSet;
 
-    use amplify::num::u5;
+    use amplify::confinement::Confined;
+    use amplify::num::{u256, u5};
     use amplify::Wrapper;
     use rand::random;
     use strict_encoding::{StreamWriter, StrictEncode};
 
+    use super::*;
     use crate::mpc::tree::test_helpers::{make_random_messages, make_random_tree};
-    use crate::mpc::MerkleBlock;
-    use crate::{CommitId, Conceal};
+    use crate::mpc::{MultiSource, MPC_MINIMAL_DEPTH};
+    use crate::{CommitId, Conceal, TryCommitVerify};
 
     #[test]
     #[should_panic(expected = "Empty")]
@@ -283,6 +313,37 @@ mod test {
         make_random_tree(&msgs);
     }
 
+    #[test]
+    fn minimal_depth() {
+        let mut source = MultiSource::with_static_entropy(0);
+        assert_eq!(source.minimal_depth(), Ok(MPC_MINIMAL_DEPTH));
+        source.min_depth = u5::ZERO;
+        assert_eq!(source.minimal_depth(), Err(Error::Empty));
+
+        for no in 1..=3u8 {
+            let protocol_id = ProtocolId::from(u256::from(no).to_le_bytes());
+            source
+                .messages
+                .insert(protocol_id, Message::from([no; 32]))
+                .unwrap();
+        }
+        assert_eq!(source.minimal_depth(), Ok(u5::with(2)));
+        assert_eq!(MerkleTree::try_commit(&source).unwrap().depth(), u5::with(2));
+        source.min_depth = MPC_MINIMAL_DEPTH;
+        assert_eq!(source.minimal_depth(), Ok(MPC_MINIMAL_DEPTH));
+
+        for size in 1..16 {
+            let msgs = make_random_messages(size);
+            let tree = make_random_tree(&msgs);
+            let source = MultiSource {
+                min_depth: u5::ZERO,
+                messages: Confined::from_iter_checked(msgs),
+                ..MultiSource::with_static_entropy(0)
+            };
+            assert_eq!(source.minimal_depth(), Ok(tree.depth()));
+        }
+    }
+
     #[test]
     fn tree_sizing() {
         for size in 1..16 {
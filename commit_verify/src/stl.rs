@@ -26,6 +26,7 @@ use crate::{mpc, MerkleHash, MerkleNode, ReservedBytes, StrictHash, LIB_NAME_COM
 pub const LIB_ID_COMMIT_VERIFY: &str =
     "stl:~lvTV1Mw-5xmhpcL-cppKeUz-ZLKBRTZ-cpYZPxp-Okxn5dk#uranium-alien-extend";
 
+#[allow(clippy::result_large_err)]
 fn _commit_verify_stl() -> Result<TypeLib, CompileError> {
     LibBuilder::new(libname!(LIB_NAME_COMMIT_VERIFY), tiny_bset! {
         strict_types::stl::std_stl().to_dependency()
@@ -0,0 +1,188 @@
+// Client-side-validation foundation libraries.
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2019-2024 by
+//     Dr. Maxim Orlovsky <orlovsky@lnp-bp.org>
+//
+// Copyright (C) 2019-2024 LNP/BP Standards Association. All rights reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Threshold (k-of-n) commitments.
+
+use std::collections::BTreeSet;
+
+use crate::merkle::{merkle_commit, MerkleHash, MerklePath};
+use crate::{CommitEncode, CommitId, StrictHash, LIB_NAME_COMMIT_VERIFY};
+
+/// Merkle tree leaf committing to the share of a party in
+/// [`ThresholdCommitment`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+#[derive(CommitEncode)]
+#[commit_encode(crate = crate, strategy = strict, id = MerkleHash)]
+pub struct ThresholdShare {
+    /// Index of the party.
+    pub party: u16,
+    /// Commitment to the share contributed by the party.
+    pub share: StrictHash,
+}
+
+/// Commitment to the shares of `n` parties, which is considered attested when
+/// at least `k` of the parties reveal their shares.
+///
+/// The shares are committed via their [`StrictHash`] commitment ids as leaves
+/// of a merkle tree, and each party receives a [`ShareProof`] of the inclusion
+/// of its share. The commitment doesn't perform any secret sharing: it is up
+/// to the protocol to define what the shares are (for instance, signatures of
+/// the parties over a common message).
+#[derive(Getters, Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(StrictDumb, StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+#[derive(CommitEncode)]
+#[commit_encode(crate = crate, strategy = strict, id = StrictHash)]
+pub struct ThresholdCommitment {
+    /// Minimal number of valid shares required for the verification.
+    #[getter(as_copy)]
+    threshold: u16,
+    /// Total number of the parties.
+    #[getter(as_copy)]
+    parties: u16,
+    /// Merkle root of the party shares.
+    #[getter(as_copy)]
+    root: MerkleHash,
+}
+
+/// Proof of inclusion of a party share into [`ThresholdCommitment`].
+#[derive(Getters, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ShareProof {
+    /// Index of the party.
+    #[getter(as_copy)]
+    party: u16,
+    /// Merkle path of the party share.
+    path: MerklePath,
+}
+
+impl ThresholdCommitment {
+    /// Commits to the shares of all parties, in the order of their indexes,
+    /// returning the commitment and the proofs for each of the parties.
+    ///
+    /// # Panics
+    ///
+    /// If the `threshold` is zero or exceeds the number of the shares, or if
+    /// there are more than `u16::MAX` shares.
+    pub fn with<'a, T>(
+        threshold: u16,
+        shares: impl IntoIterator<Item = &'a T>,
+    ) -> (Self, Vec<ShareProof>)
+    where
+        T: CommitEncode<CommitmentId = StrictHash> + 'a,
+    {
+        let leaves = shares
+            .into_iter()
+            .enumerate()
+            .map(|(party, share)| ThresholdShare {
+                party: u16::try_from(party).expect("too many parties"),
+                share: share.commit_id(),
+            })
+            .collect::<Vec<_>>();
+        let parties = leaves.len() as u16;
+        assert!(
+            threshold > 0 && threshold <= parties,
+            "threshold {threshold} is invalid for {parties} parties"
+        );
+
+        let (root, handle) = merkle_commit(leaves);
+        let proofs = (0..parties)
+            .map(|party| ShareProof {
+                party,
+                path: handle
+                    .proof(party as u32)
+                    .expect("party index within the tree width"),
+            })
+            .collect();
+        let commitment = ThresholdCommitment {
+            threshold,
+            parties,
+            root,
+        };
+        (commitment, proofs)
+    }
+
+    /// Verifies that the provided shares contain at least
+    /// [`Self::threshold`] valid shares of distinct parties.
+    ///
+    /// Shares which fail the verification are ignored; multiple shares of the
+    /// same party are counted once.
+    pub fn verify<'a>(
+        &self,
+        shares: impl IntoIterator<Item = (StrictHash, &'a ShareProof)>,
+    ) -> bool {
+        let valid = shares
+            .into_iter()
+            .filter(|(share, proof)| {
+                proof.party < self.parties &&
+                    proof.path.pos() == proof.party as u32 &&
+                    proof.path.width() == self.parties as u32 &&
+                    proof.path.verify(
+                        ThresholdShare {
+                            party: proof.party,
+                            share: *share,
+                        }
+                        .commit_id(),
+                        self.root,
+                    )
+            })
+            .map(|(_, proof)| proof.party)
+            .collect::<BTreeSet<_>>();
+        valid.len() >= self.threshold as usize
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CommitEngine;
+
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    #[derive(StrictType, StrictEncode, StrictDecode, StrictDumb)]
+    #[strict_type(lib = LIB_NAME_COMMIT_VERIFY)]
+    struct Attestation(u64);
+
+    impl CommitEncode for Attestation {
+        type CommitmentId = StrictHash;
+        fn commit_encode(&self, e: &mut CommitEngine) { e.commit_to_serialized(self); }
+    }
+
+    #[test]
+    fn two_of_three() {
+        let shares = [Attestation(1), Attestation(2), Attestation(3)];
+        let ids = shares.map(|share| share.commit_id());
+        let (commitment, proofs) = ThresholdCommitment::with(2, &shares);
+        assert_eq!(commitment.threshold(), 2);
+        assert_eq!(commitment.parties(), 3);
+        assert_eq!(proofs.len(), 3);
+
+        assert!(commitment.verify([(ids[0], &proofs[0]), (ids[2], &proofs[2])]));
+        assert!(commitment.verify(ids.iter().copied().zip(&proofs)));
+        assert!(!commitment.verify([(ids[1], &proofs[1])]));
+        assert!(!commitment.verify([(ids[1], &proofs[1]), (ids[1], &proofs[1])]));
+        assert!(!commitment.verify([(ids[0], &proofs[0]), (ids[0], &proofs[1])]));
+
+        let (other, _) = ThresholdCommitment::with(3, &shares);
+        assert_eq!(other.root(), commitment.root());
+        assert_ne!(other.commit_id(), commitment.commit_id());
+    }
+}
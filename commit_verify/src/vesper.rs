@@ -33,10 +33,18 @@ pub type VesperCommit = TExpr<Pred>;
 #[display(lowercase)]
 pub enum Pred {
     Commitment,
+    Domain,
     Serialized,
     Hashed,
     Merklized,
     Concealed,
+    Signature,
+    Variant,
+    Raw,
+    Version,
+    Schema,
+    #[display("nonZero")]
+    NonZero,
     List,
     Set,
     Element,
@@ -92,11 +100,22 @@ impl Attribute for Attr {
 impl CommitStep {
     fn subject(&self) -> Ident {
         match self {
+            CommitStep::Raw => return ident!("Bytes"),
+            CommitStep::NonZero(8) => return ident!("U8"),
+            CommitStep::NonZero(16) => return ident!("U16"),
+            CommitStep::NonZero(32) => return ident!("U32"),
+            CommitStep::NonZero(64) => return ident!("U64"),
+            CommitStep::NonZero(_) => return ident!("Int"),
+            CommitStep::Domain(fqn) => fqn,
             CommitStep::Serialized(fqn) => fqn,
             CommitStep::Collection(_, _, fqn) => fqn,
             CommitStep::Hashed(fqn) => fqn,
             CommitStep::Merklized(fqn) => fqn,
             CommitStep::Concealed(fqn) => fqn,
+            CommitStep::DetachedSignature(fqn) => fqn,
+            CommitStep::Variant(fqn) => fqn,
+            CommitStep::Version(fqn, _) => fqn,
+            CommitStep::Schema(fqn, _) => fqn,
         }
         .name
         .to_ident()
@@ -104,6 +123,7 @@ impl CommitStep {
 
     fn predicate(&self) -> Pred {
         match self {
+            CommitStep::Domain(_) => Pred::Domain,
             CommitStep::Serialized(_) => Pred::Serialized,
             CommitStep::Collection(CommitColType::List, _, _) => Pred::List,
             CommitStep::Collection(CommitColType::Set, _, _) => Pred::Set,
@@ -111,6 +131,12 @@ impl CommitStep {
             CommitStep::Hashed(_) => Pred::Hashed,
             CommitStep::Merklized(_) => Pred::Merklized,
             CommitStep::Concealed(_) => Pred::Concealed,
+            CommitStep::DetachedSignature(_) => Pred::Signature,
+            CommitStep::Variant(_) => Pred::Variant,
+            CommitStep::Raw => Pred::Raw,
+            CommitStep::Version(..) => Pred::Version,
+            CommitStep::Schema(..) => Pred::Schema,
+            CommitStep::NonZero(_) => Pred::NonZero,
         }
     }
 
@@ -118,7 +144,16 @@ impl CommitStep {
         match self {
             CommitStep::Collection(_, sizing, _) => small_vec![Attr::LenRange((*sizing).into())],
             CommitStep::Concealed(from) => small_vec![Attr::Concealed(from.clone())],
-            CommitStep::Serialized(_) | CommitStep::Hashed(_) | CommitStep::Merklized(_) => none!(),
+            CommitStep::Domain(_) |
+            CommitStep::Serialized(_) |
+            CommitStep::Hashed(_) |
+            CommitStep::Merklized(_) |
+            CommitStep::DetachedSignature(_) |
+            CommitStep::Variant(_) |
+            CommitStep::Raw |
+            CommitStep::Version(..) |
+            CommitStep::Schema(..) |
+            CommitStep::NonZero(_) => none!(),
         }
     }
 
@@ -152,10 +187,17 @@ impl CommitStep {
                     })
                 ]
             }
+            CommitStep::Domain(_) |
             CommitStep::Serialized(_) |
             CommitStep::Hashed(_) |
             CommitStep::Merklized(_) |
-            CommitStep::Concealed(_) => empty!(),
+            CommitStep::Concealed(_) |
+            CommitStep::DetachedSignature(_) |
+            CommitStep::Variant(_) |
+            CommitStep::Raw |
+            CommitStep::Version(..) |
+            CommitStep::Schema(..) |
+            CommitStep::NonZero(_) => empty!(),
         }
     }
 }
@@ -71,7 +71,8 @@
 //!   or "unknown". Some specific implementations of single-use-seals may define
 //!   procedure to deterministically prove that a given seal is not closed (i.e.
 //!   opened), however this is not a part of the specification, and we should
-//!   not rely on the existence of such possibility in all cases.
+//!   not rely on the existence of such possibility in all cases. Such
+//!   implementations may expose this ability via [`ProvableOpen`] trait.
 //!
 //! ## Trait structure
 //!
@@ -103,6 +104,7 @@
 //!   and Proof-of-Publication. 1. Single-Use-Seal Definition.
 //!   <https://petertodd.org/2017/scalable-single-use-seal-asset-transfer>
 
+extern crate alloc;
 #[cfg(feature = "strict_encoding")]
 #[macro_use]
 extern crate strict_encoding;
@@ -111,12 +113,16 @@ extern crate strict_encoding;
 #[macro_use]
 extern crate serde;
 
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
 use core::borrow::Borrow;
 use core::convert::Infallible;
 use core::error::Error;
 use core::fmt::{self, Debug, Display, Formatter};
 use core::marker::PhantomData;
 
+#[cfg(feature = "pub_id_repr")]
+use sha2::{Digest, Sha256};
 #[cfg(feature = "strict_encoding")]
 use strict_encoding::{StrictDecode, StrictDumb, StrictEncode};
 
@@ -159,6 +165,65 @@ pub trait SingleUseSeal:
     type CliWitness: ClientSideWitness<Seal = Self> + StrictDumb + StrictEncode + StrictDecode;
 
     fn is_included(&self, message: Self::Message, witness: &SealWitness<Self>) -> bool;
+
+    /// Checks whether the seal definition refers to an existing place in the
+    /// medium, using information provided by the `resolver`.
+    ///
+    /// Seals defined over places which doesn't exist yet (like
+    /// deterministically derived future outputs) are
+    /// [`DefinitionStatus::Pending`]; such seals can't be closed, so a
+    /// failing [`SealWitness::verify_seal_closing`] for them doesn't
+    /// indicate that the seal is malformed.
+    ///
+    /// By default, queries the resolver; implementations detecting malformed
+    /// definitions on their own should override the method.
+    fn definition_status(&self, resolver: &impl DefinitionResolver<Self>) -> DefinitionStatus {
+        resolver.definition_status(self)
+    }
+}
+
+/// Status of a single-use-seal definition in the medium.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "camelCase"))]
+pub enum DefinitionStatus {
+    /// The seal is defined over an existing place in the medium and can be
+    /// closed.
+    Resolved,
+    /// The seal is defined over a place which doesn't exist yet, but may
+    /// appear in the future.
+    Pending,
+    /// The seal definition is malformed and the seal can't be ever closed.
+    Invalid,
+}
+
+impl Display for DefinitionStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DefinitionStatus::Resolved => f.write_str("resolved"),
+            DefinitionStatus::Pending => f.write_str("pending"),
+            DefinitionStatus::Invalid => f.write_str("invalid"),
+        }
+    }
+}
+
+/// Resolver providing information about the places in the medium over which
+/// single-use-seals are defined.
+pub trait DefinitionResolver<Seal: SingleUseSeal> {
+    /// Returns status of the given seal definition.
+    fn definition_status(&self, seal: &Seal) -> DefinitionStatus;
+}
+
+/// Single-use-seals which can be deterministically proven to be open, i.e. not
+/// closed over any message.
+///
+/// Not all single-use-seal implementations support such proofs, thus the trait
+/// is optional and doesn't affect seal closing verification.
+pub trait ProvableOpen: SingleUseSeal {
+    /// Proof that the seal is not closed.
+    type OpenProof;
+
+    /// Verifies that the provided `proof` demonstrates that the seal is open.
+    fn verify_open(&self, proof: &Self::OpenProof) -> bool;
 }
 
 pub trait ClientSideWitness: Eq {
@@ -210,12 +275,111 @@ pub trait PublishedWitness<Seal: SingleUseSeal> {
     type Error: Clone + Error;
 
     fn pub_id(&self) -> Self::PubId;
+
+    /// Returns protocol-independent representation of the publication id,
+    /// which can be used as a common key for witnesses from different
+    /// protocols.
+    ///
+    /// The `protocol` is the name of the publication medium, which must be
+    /// stable and unique among the protocols sharing the key space. By
+    /// default, computed with [`PubIdRepr::from_display`].
+    #[cfg(feature = "pub_id_repr")]
+    fn pub_id_repr(&self, protocol: &str) -> PubIdRepr {
+        PubIdRepr::from_display(protocol, &self.pub_id())
+    }
+
+    /// Reports how deep the witness publication is buried in the medium,
+    /// using information provided by the `resolver`.
+    ///
+    /// By default, queries the resolver with [`Self::pub_id`].
+    #[cfg(feature = "confirmations")]
+    fn confirmation_status(
+        &self,
+        resolver: &impl ConfirmationResolver<Self::PubId>,
+    ) -> ConfirmationStatus {
+        resolver.confirmation_status(self.pub_id())
+    }
+
     fn verify_commitment(
         &self,
         proof: <Seal::CliWitness as ClientSideWitness>::Proof,
     ) -> Result<(), Self::Error>;
 }
 
+/// Status of the witness publication in the medium.
+#[cfg(feature = "confirmations")]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "camelCase"))]
+pub struct ConfirmationStatus {
+    /// Number of confirmations of the publication; zero for unconfirmed or
+    /// unknown publications.
+    pub confirmations: u32,
+    /// Whether the publication is buried deep enough to be considered safe
+    /// against reorganizations of the medium.
+    pub reorg_safe: bool,
+}
+
+/// Resolver providing information about the status of publications in the
+/// medium.
+#[cfg(feature = "confirmations")]
+pub trait ConfirmationResolver<PubId> {
+    /// Returns status of the publication with the given id.
+    fn confirmation_status(&self, pub_id: PubId) -> ConfirmationStatus;
+}
+
+/// Protocol-independent 32-byte representation of a publication id.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+#[cfg_attr(
+    feature = "strict_encoding",
+    derive(StrictType, StrictEncode, StrictDecode),
+    strict_type(lib = "SingleUseSeals")
+)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct PubIdRepr(pub [u8; 32]);
+
+#[cfg(feature = "pub_id_repr")]
+impl PubIdRepr {
+    /// Tag of the hash used to construct the representation.
+    pub const TAG: &'static str = "urn:lnp-bp:seals:pub-id#2026-10-14";
+
+    /// Constructs representation as a tagged hash of the `protocol` name and
+    /// [`Display`] output of the `id`, such that publication ids of different
+    /// protocols never produce the same representation, even if their textual
+    /// forms match.
+    pub fn from_display(protocol: &str, id: &impl Display) -> Self {
+        struct HashWriter(Sha256);
+        impl fmt::Write for HashWriter {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0.update(s.as_bytes());
+                Ok(())
+            }
+        }
+
+        let tag = Sha256::digest(Self::TAG.as_bytes());
+        let mut hasher = Sha256::new();
+        hasher.update(tag);
+        hasher.update(tag);
+        hasher.update((protocol.len() as u64).to_le_bytes());
+        hasher.update(protocol.as_bytes());
+        let mut writer = HashWriter(hasher);
+        fmt::write(&mut writer, format_args!("{id}")).expect("hash writer doesn't fail");
+        Self(writer.0.finalize().into())
+    }
+}
+
+impl From<[u8; 32]> for PubIdRepr {
+    fn from(bytes: [u8; 32]) -> Self { Self(bytes) }
+}
+
+impl Display for PubIdRepr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
 /// Seal closing witness.
 #[derive(Clone, Copy)]
 #[cfg_attr(
@@ -258,6 +422,17 @@ where Seal: SingleUseSeal
         self.verify_seals_closing([seal], message)
     }
 
+    /// Performs cheap structural check that the witness references the `seal`
+    /// for the `message`, using [`SingleUseSeal::is_included`] only.
+    ///
+    /// This is NOT a verification of the seal closing: the cryptographic
+    /// commitment to the message is not checked, so the witness may still be
+    /// invalid. The method is intended for filtering out irrelevant witnesses
+    /// before running [`Self::verify_seal_closing`].
+    pub fn verify_structure(&self, seal: impl Borrow<Seal>, message: Seal::Message) -> bool {
+        seal.borrow().is_included(message, self)
+    }
+
     pub fn verify_seals_closing(
         &self,
         seals: impl IntoIterator<Item = impl Borrow<Seal>>,
@@ -280,6 +455,181 @@ where Seal: SingleUseSeal
             .verify_commitment(f_msg)
             .map_err(SealError::Published)
     }
+
+    /// Verifies closing of the `seal` over the `message`, additionally
+    /// requiring the witness publication to have at least `min` confirmations,
+    /// as reported by the `resolver`.
+    #[cfg(feature = "confirmations")]
+    pub fn verify_with_min_confirmations(
+        &self,
+        min: u32,
+        resolver: &impl ConfirmationResolver<<Seal::PubWitness as PublishedWitness<Seal>>::PubId>,
+        seal: impl Borrow<Seal>,
+        message: Seal::Message,
+    ) -> Result<ConfirmationStatus, SealError<Seal>> {
+        self.verify_seal_closing(seal, message)?;
+        let status = self.published.confirmation_status(resolver);
+        if status.confirmations < min {
+            return Err(SealError::Unconfirmed(self.published.pub_id(), status));
+        }
+        Ok(status)
+    }
+}
+
+/// Conceals the client-side part of the witness, keeping the published part,
+/// such that the witness can be shared without revealing private client-side
+/// data.
+///
+/// The concealed witness still verifies the closing of the seals for
+/// protocols where the concealed client-side witness produces the same proof
+/// as the original one.
+#[cfg(feature = "conceal")]
+impl<Seal> commit_verify::Conceal for SealWitness<Seal>
+where
+    Seal: SingleUseSeal,
+    Seal::PubWitness: Clone,
+    Seal::CliWitness: commit_verify::Conceal<Concealed = Seal::CliWitness>,
+{
+    type Concealed = Self;
+
+    fn conceal(&self) -> Self::Concealed {
+        SealWitness::new(self.published.clone(), self.client.conceal())
+    }
+}
+
+/// Witness of closing multiple seals, each over its own message, by a single
+/// publication.
+///
+/// Each of the seals has its own client-side witness, while the published
+/// witness is shared by all of them.
+pub struct AggregateWitness<Seal: SingleUseSeal> {
+    pub published: Seal::PubWitness,
+    pub closings: Vec<(Seal, Seal::Message, Seal::CliWitness)>,
+}
+
+impl<Seal: SingleUseSeal> AggregateWitness<Seal> {
+    pub fn new(published: Seal::PubWitness) -> Self {
+        Self {
+            published,
+            closings: Vec::new(),
+        }
+    }
+
+    /// Adds information about closing of the `seal` over the `message` with
+    /// the given client-side witness.
+    pub fn push(&mut self, seal: Seal, message: Seal::Message, client: Seal::CliWitness) {
+        self.closings.push((seal, message, client));
+    }
+
+    /// Verifies closing of each of the seals over its message, collecting
+    /// errors for all the seals which fail the verification.
+    pub fn verify_all(&self) -> Result<(), Vec<SealError<Seal>>>
+    where
+        Seal::PubWitness: Clone,
+        Seal::CliWitness: Clone,
+    {
+        let errors = self
+            .closings
+            .iter()
+            .filter_map(|(seal, message, client)| {
+                SealWitness::new(self.published.clone(), client.clone())
+                    .verify_seal_closing(seal.clone(), *message)
+                    .err()
+            })
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(())
+    }
+}
+
+/// Conflict between witnesses closing the same seal over different messages,
+/// i.e. an attempted double-spend.
+pub struct Conflict<Seal: SingleUseSeal> {
+    /// Seal closed more than once.
+    pub seal: Seal,
+    /// All valid closings of the seal, with the message each of the witnesses
+    /// closes the seal over. Contains at least two distinct messages.
+    pub closings: Vec<(Seal::Message, SealWitness<Seal>)>,
+}
+
+/// Detects seals which are closed over two or more distinct messages by valid
+/// witnesses from the provided set.
+///
+/// Witnesses which fail to verify the closing of their seal over the message
+/// are ignored. The conflicts are reported in the order of the seals.
+pub fn detect_conflicts<Seal>(
+    witnesses: impl IntoIterator<Item = (SealWitness<Seal>, Seal, Seal::Message)>,
+) -> Vec<Conflict<Seal>>
+where Seal: SingleUseSeal + Ord {
+    let mut closings = BTreeMap::<Seal, Vec<(Seal::Message, SealWitness<Seal>)>>::new();
+    for (witness, seal, message) in witnesses {
+        if witness.verify_seal_closing(&seal, message).is_ok() {
+            closings.entry(seal).or_default().push((message, witness));
+        }
+    }
+    closings
+        .into_iter()
+        .filter(|(_, closings)| {
+            closings
+                .iter()
+                .any(|(message, _)| *message != closings[0].0)
+        })
+        .map(|(seal, closings)| Conflict { seal, closings })
+        .collect()
+}
+
+/// Resolver detecting which of the publications belong to the currently best
+/// chain of the medium (for instance, after a blockchain reorganization).
+pub trait CanonicalResolver<PubId> {
+    /// Detects whether the publication with the given id is canonical.
+    fn is_canonical(&self, pub_id: PubId) -> bool;
+}
+
+/// Ordered candidate witnesses for the closing of the same seals, only one of
+/// which may be a part of the best chain of the medium at a time.
+///
+/// Used to handle reorganizations of the medium, during which the published
+/// witness of a seal closing may change.
+pub struct WitnessChain<Seal: SingleUseSeal> {
+    /// Candidate witnesses, in the order of their priority.
+    pub candidates: Vec<SealWitness<Seal>>,
+}
+
+impl<Seal: SingleUseSeal> Default for WitnessChain<Seal> {
+    fn default() -> Self { Self::new() }
+}
+
+impl<Seal: SingleUseSeal> WitnessChain<Seal> {
+    pub fn new() -> Self {
+        Self {
+            candidates: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, witness: SealWitness<Seal>) { self.candidates.push(witness); }
+
+    /// Verifies closing of the `seal` over the `message` by the first of the
+    /// candidate witnesses which is canonical according to the `resolver`,
+    /// returning its publication id.
+    ///
+    /// The remaining candidates are not verified, even if the canonical one
+    /// fails the verification.
+    pub fn verify_best(
+        &self,
+        seal: impl Borrow<Seal>,
+        message: Seal::Message,
+        resolver: &impl CanonicalResolver<<Seal::PubWitness as PublishedWitness<Seal>>::PubId>,
+    ) -> Result<<Seal::PubWitness as PublishedWitness<Seal>>::PubId, SealError<Seal>> {
+        let witness = self
+            .candidates
+            .iter()
+            .find(|witness| resolver.is_canonical(witness.published.pub_id()))
+            .ok_or(SealError::NoCanonicalWitness)?;
+        witness.verify_seal_closing(seal, message)?;
+        Ok(witness.published.pub_id())
+    }
 }
 
 #[derive(Clone)]
@@ -287,6 +637,9 @@ pub enum SealError<Seal: SingleUseSeal> {
     NotIncluded(Seal, <Seal::PubWitness as PublishedWitness<Seal>>::PubId),
     Published(<Seal::PubWitness as PublishedWitness<Seal>>::Error),
     Client(<Seal::CliWitness as ClientSideWitness>::Error),
+    NoCanonicalWitness,
+    #[cfg(feature = "confirmations")]
+    Unconfirmed(<Seal::PubWitness as PublishedWitness<Seal>>::PubId, ConfirmationStatus),
 }
 
 impl<Seal: SingleUseSeal> Debug for SealError<Seal> {
@@ -299,6 +652,13 @@ impl<Seal: SingleUseSeal> Debug for SealError<Seal> {
                 .finish(),
             SealError::Published(err) => f.debug_tuple("SealError::Published").field(err).finish(),
             SealError::Client(err) => f.debug_tuple("SealError::Client(err").field(err).finish(),
+            SealError::NoCanonicalWitness => f.write_str("SealError::NoCanonicalWitness"),
+            #[cfg(feature = "confirmations")]
+            SealError::Unconfirmed(pub_id, status) => f
+                .debug_tuple("SealError::Unconfirmed")
+                .field(pub_id)
+                .field(status)
+                .finish(),
         }
     }
 }
@@ -311,7 +671,68 @@ impl<Seal: SingleUseSeal> Display for SealError<Seal> {
             }
             SealError::Published(err) => Display::fmt(err, f),
             SealError::Client(err) => Display::fmt(err, f),
+            SealError::NoCanonicalWitness => {
+                f.write_str("none of the candidate witnesses is on the best chain")
+            }
+            #[cfg(feature = "confirmations")]
+            SealError::Unconfirmed(pub_id, status) => {
+                write!(f, "witness {pub_id} has only {} confirmations", status.confirmations)
+            }
+        }
+    }
+}
+
+/// Serializes the error as a structured object with `kind` field set to the
+/// camel-cased name of the error variant, and `seal`, `pub_id` and `detail`
+/// fields, which are `null` when not applicable to the variant. The `detail`
+/// field contains serialized inner error of the published or client-side
+/// witness.
+#[cfg(feature = "serde")]
+impl<Seal: SingleUseSeal> serde::Serialize for SealError<Seal>
+where
+    Seal: serde::Serialize,
+    <Seal::PubWitness as PublishedWitness<Seal>>::PubId: serde::Serialize,
+    <Seal::PubWitness as PublishedWitness<Seal>>::Error: serde::Serialize,
+    <Seal::CliWitness as ClientSideWitness>::Error: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("SealError", 4)?;
+        match self {
+            SealError::NotIncluded(seal, pub_id) => {
+                s.serialize_field("kind", "notIncluded")?;
+                s.serialize_field("seal", &Some(seal))?;
+                s.serialize_field("pub_id", &Some(pub_id))?;
+                s.serialize_field("detail", &None::<()>)?;
+            }
+            SealError::Published(err) => {
+                s.serialize_field("kind", "published")?;
+                s.serialize_field("seal", &None::<()>)?;
+                s.serialize_field("pub_id", &None::<()>)?;
+                s.serialize_field("detail", &Some(err))?;
+            }
+            SealError::Client(err) => {
+                s.serialize_field("kind", "client")?;
+                s.serialize_field("seal", &None::<()>)?;
+                s.serialize_field("pub_id", &None::<()>)?;
+                s.serialize_field("detail", &Some(err))?;
+            }
+            SealError::NoCanonicalWitness => {
+                s.serialize_field("kind", "noCanonicalWitness")?;
+                s.serialize_field("seal", &None::<()>)?;
+                s.serialize_field("pub_id", &None::<()>)?;
+                s.serialize_field("detail", &None::<()>)?;
+            }
+            #[cfg(feature = "confirmations")]
+            SealError::Unconfirmed(pub_id, status) => {
+                s.serialize_field("kind", "unconfirmed")?;
+                s.serialize_field("seal", &None::<()>)?;
+                s.serialize_field("pub_id", &Some(pub_id))?;
+                s.serialize_field("detail", &Some(status))?;
+            }
         }
+        s.end()
     }
 }
 
@@ -325,6 +746,413 @@ where
             SealError::NotIncluded(..) => None,
             SealError::Published(e) => Some(e),
             SealError::Client(e) => Some(e),
+            SealError::NoCanonicalWitness => None,
+            #[cfg(feature = "confirmations")]
+            SealError::Unconfirmed(..) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    #[cfg_attr(feature = "serde", derive(Serialize), serde(rename_all = "camelCase"))]
+    pub enum MockError {
+        Commitment,
+        Convolution,
+    }
+
+    impl Display for MockError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            match self {
+                MockError::Commitment => f.write_str("invalid commitment"),
+                MockError::Convolution => f.write_str("invalid message"),
+            }
+        }
+    }
+
+    impl Error for MockError {}
+
+    /// Seal defined by its number, which may be closed by a publication
+    /// listing the seal number in its bitmask.
+    #[derive(Copy, Clone, Ord, PartialOrd, PartialEq, Eq, Debug, Default)]
+    #[cfg_attr(
+        feature = "strict_encoding",
+        derive(StrictType, StrictEncode, StrictDecode),
+        strict_type(lib = "Test")
+    )]
+    #[cfg_attr(feature = "serde", derive(Serialize), serde(transparent))]
+    pub struct MockSeal(pub u8);
+
+    impl Display for MockSeal {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { write!(f, "seal#{}", self.0) }
+    }
+
+    impl SingleUseSeal for MockSeal {
+        type Message = u8;
+        type PubWitness = MockPublished;
+        type CliWitness = MockClient;
+
+        fn is_included(&self, _: Self::Message, witness: &SealWitness<Self>) -> bool {
+            witness.published.includes(*self)
+        }
+
+        fn definition_status(&self, resolver: &impl DefinitionResolver<Self>) -> DefinitionStatus {
+            // Publication bitmask can't contain seals with larger numbers
+            if self.0 >= 32 {
+                return DefinitionStatus::Invalid;
+            }
+            resolver.definition_status(self)
+        }
+    }
+
+    impl ProvableOpen for MockSeal {
+        type OpenProof = MockPublished;
+
+        fn verify_open(&self, proof: &Self::OpenProof) -> bool { !proof.includes(*self) }
+    }
+
+    #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+    #[cfg_attr(
+        feature = "strict_encoding",
+        derive(StrictType, StrictEncode, StrictDecode),
+        strict_type(lib = "Test")
+    )]
+    pub struct MockPublished {
+        pub id: u8,
+        pub seals: u32,
+        pub commitment: u8,
+    }
+
+    impl MockPublished {
+        pub fn includes(&self, seal: MockSeal) -> bool { self.seals & (1 << seal.0) != 0 }
+    }
+
+    /// Publication in a different medium, using textual publication ids.
+    #[cfg(feature = "pub_id_repr")]
+    pub(crate) struct MockNamedPublished(pub &'static str);
+
+    #[cfg(feature = "pub_id_repr")]
+    impl PublishedWitness<MockSeal> for MockNamedPublished {
+        type PubId = &'static str;
+        type Error = MockError;
+
+        fn pub_id(&self) -> Self::PubId { self.0 }
+
+        fn verify_commitment(&self, _: u8) -> Result<(), Self::Error> { Ok(()) }
+    }
+
+    impl PublishedWitness<MockSeal> for MockPublished {
+        type PubId = u8;
+        type Error = MockError;
+
+        fn pub_id(&self) -> Self::PubId { self.id }
+
+        fn verify_commitment(&self, proof: u8) -> Result<(), Self::Error> {
+            if proof != self.commitment {
+                return Err(MockError::Commitment);
+            }
+            Ok(())
+        }
+    }
+
+    /// Client-side witness tweaking the message before it is committed to.
+    /// Messages equal to `0xFF` are not allowed.
+    #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+    #[cfg_attr(
+        feature = "strict_encoding",
+        derive(StrictType, StrictEncode, StrictDecode),
+        strict_type(lib = "Test")
+    )]
+    pub struct MockClient(pub u8);
+
+    impl ClientSideWitness for MockClient {
+        type Seal = MockSeal;
+        type Proof = u8;
+        type Error = MockError;
+
+        fn convolve_commit(&self, msg: u8) -> Result<Self::Proof, Self::Error> {
+            if msg == 0xFF {
+                return Err(MockError::Convolution);
+            }
+            Ok(msg.wrapping_add(self.0))
+        }
+
+        fn merge(&mut self, _: Self) -> Result<(), impl Error>
+        where Self: Sized {
+            Ok::<_, MockError>(())
+        }
+    }
+
+    /// Concealing keeps the tweak, which is required to produce the proof, and
+    /// hides nothing else.
+    #[cfg(feature = "conceal")]
+    impl commit_verify::Conceal for MockClient {
+        type Concealed = Self;
+
+        fn conceal(&self) -> Self { *self }
+    }
+
+    /// Constructs the witness of closing the given seals over a message.
+    pub fn witness(id: u8, seals: &[u8], msg: u8, tweak: u8) -> SealWitness<MockSeal> {
+        let published = MockPublished {
+            id,
+            seals: seals.iter().fold(0, |mask, seal| mask | (1 << seal)),
+            commitment: msg.wrapping_add(tweak),
+        };
+        SealWitness::new(published, MockClient(tweak))
+    }
+
+    #[test]
+    fn seal_closing() {
+        let witness = witness(1, &[1, 3], 0xA0, 7);
+        assert!(witness.verify_seal_closing(MockSeal(1), 0xA0).is_ok());
+        assert!(witness
+            .verify_seals_closing([MockSeal(1), MockSeal(3)], 0xA0)
+            .is_ok());
+        assert!(matches!(
+            witness.verify_seal_closing(MockSeal(2), 0xA0),
+            Err(SealError::NotIncluded(MockSeal(2), 1))
+        ));
+        assert!(matches!(
+            witness.verify_seal_closing(MockSeal(1), 0xA1),
+            Err(SealError::Published(MockError::Commitment))
+        ));
+        assert!(matches!(
+            witness.verify_seal_closing(MockSeal(1), 0xFF),
+            Err(SealError::Client(MockError::Convolution))
+        ));
+    }
+
+    #[test]
+    fn structure() {
+        // Witness commits to a different message
+        let witness = witness(1, &[1, 3], 0xB0, 7);
+        assert!(witness.verify_structure(MockSeal(1), 0xA0));
+        assert!(!witness.verify_structure(MockSeal(2), 0xA0));
+        assert!(matches!(
+            witness.verify_seal_closing(MockSeal(1), 0xA0),
+            Err(SealError::Published(MockError::Commitment))
+        ));
+    }
+
+    #[test]
+    fn aggregate() {
+        let published = witness(1, &[1, 2, 3], 0, 30).published;
+        let mut aggregate = AggregateWitness::new(published);
+        aggregate.push(MockSeal(1), 10, MockClient(20));
+        aggregate.push(MockSeal(2), 20, MockClient(10));
+        aggregate.push(MockSeal(3), 30, MockClient(0));
+        assert!(aggregate.verify_all().is_ok());
+
+        aggregate.push(MockSeal(4), 30, MockClient(0));
+        aggregate.push(MockSeal(1), 11, MockClient(20));
+        let errors = aggregate.verify_all().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], SealError::NotIncluded(MockSeal(4), 1)));
+        assert!(matches!(errors[1], SealError::Published(MockError::Commitment)));
+    }
+
+    #[test]
+    fn conflicts() {
+        let consistent = [
+            (witness(1, &[1, 2], 0xA0, 7), MockSeal(1), 0xA0),
+            (witness(2, &[1], 0xA0, 3), MockSeal(1), 0xA0),
+            (witness(1, &[1, 2], 0xA0, 7), MockSeal(2), 0xA0),
+        ];
+        assert!(detect_conflicts(consistent).is_empty());
+
+        let conflicting = [
+            (witness(1, &[1, 2], 0xA0, 7), MockSeal(1), 0xA0),
+            (witness(2, &[1], 0xB0, 7), MockSeal(1), 0xB0),
+            (witness(1, &[1, 2], 0xA0, 7), MockSeal(2), 0xA0),
+            // invalid witness which is ignored
+            (witness(3, &[2], 0xC0, 7), MockSeal(2), 0xC1),
+        ];
+        let conflicts = detect_conflicts(conflicting);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].seal, MockSeal(1));
+        let messages = conflicts[0]
+            .closings
+            .iter()
+            .map(|(message, witness)| (*message, witness.published.id))
+            .collect::<Vec<_>>();
+        assert_eq!(messages, [(0xA0, 1), (0xB0, 2)]);
+    }
+
+    struct MockCanonical(&'static [u8]);
+
+    impl CanonicalResolver<u8> for MockCanonical {
+        fn is_canonical(&self, pub_id: u8) -> bool { self.0.contains(&pub_id) }
+    }
+
+    #[test]
+    fn witness_chain() {
+        let mut chain = WitnessChain::new();
+        chain.push(witness(1, &[2], 0xA0, 7));
+        chain.push(witness(2, &[1, 2], 0xA0, 7));
+
+        assert!(matches!(chain.verify_best(MockSeal(1), 0xA0, &MockCanonical(&[2])), Ok(2)));
+        assert!(matches!(chain.verify_best(MockSeal(2), 0xA0, &MockCanonical(&[1, 2])), Ok(1)));
+        assert!(matches!(
+            chain.verify_best(MockSeal(1), 0xA0, &MockCanonical(&[1])),
+            Err(SealError::NotIncluded(MockSeal(1), 1))
+        ));
+        assert!(matches!(
+            chain.verify_best(MockSeal(1), 0xA0, &MockCanonical(&[3])),
+            Err(SealError::NoCanonicalWitness)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "conceal")]
+    fn conceal() {
+        use commit_verify::Conceal;
+
+        let witness = witness(1, &[1, 3], 0xA0, 7);
+        let concealed = witness.conceal();
+        assert_eq!(concealed.published, witness.published);
+        assert!(concealed.verify_seal_closing(MockSeal(1), 0xA0).is_ok());
+        assert!(concealed.verify_seal_closing(MockSeal(2), 0xA0).is_err());
+    }
+
+    /// Resolver knowing all seals up to the given number; seals with greater
+    /// numbers are defined over future publications.
+    struct MockDefinitions(u8);
+
+    impl DefinitionResolver<MockSeal> for MockDefinitions {
+        fn definition_status(&self, seal: &MockSeal) -> DefinitionStatus {
+            if seal.0 <= self.0 {
+                DefinitionStatus::Resolved
+            } else {
+                DefinitionStatus::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn definition_status() {
+        let resolver = MockDefinitions(4);
+        assert_eq!(MockSeal(1).definition_status(&resolver), DefinitionStatus::Resolved);
+        assert_eq!(MockSeal(4).definition_status(&resolver), DefinitionStatus::Resolved);
+        assert_eq!(MockSeal(5).definition_status(&resolver), DefinitionStatus::Pending);
+        assert_eq!(MockSeal(32).definition_status(&resolver), DefinitionStatus::Invalid);
+        assert_eq!(DefinitionStatus::Pending.to_string(), "pending");
+
+        // Pending seal can't be closed yet
+        let witness = witness(2, &[1, 4], 0xA0, 7);
+        assert!(matches!(
+            witness.verify_seal_closing(MockSeal(5), 0xA0),
+            Err(SealError::NotIncluded(MockSeal(5), 2))
+        ));
+    }
+
+    #[test]
+    fn open_proof() {
+        let published = witness(1, &[1, 3], 0xA0, 7).published;
+        assert!(MockSeal(2).verify_open(&published));
+        assert!(!MockSeal(3).verify_open(&published));
+    }
+
+    #[test]
+    #[cfg(feature = "pub_id_repr")]
+    fn pub_id_repr() {
+        const MOCK: &str = "mock";
+        const NAMED: &str = "named";
+
+        let published = witness(1, &[1], 0xA0, 7).published;
+        let named = MockNamedPublished("tx01");
+        assert_ne!(published.pub_id_repr(MOCK), named.pub_id_repr(NAMED));
+        // Same textual ids from different protocols must not collide
+        assert_eq!(published.pub_id().to_string(), MockNamedPublished("1").pub_id());
+        assert_ne!(published.pub_id_repr(MOCK), MockNamedPublished("1").pub_id_repr(NAMED));
+        assert_ne!(PubIdRepr::from_display("a", &1), PubIdRepr::from_display("b", &1));
+        assert_eq!(named.pub_id_repr(NAMED), MockNamedPublished("tx01").pub_id_repr(NAMED));
+        assert_ne!(named.pub_id_repr(NAMED), MockNamedPublished("tx02").pub_id_repr(NAMED));
+        assert_eq!(named.pub_id_repr(NAMED).to_string().len(), 64);
+    }
+
+    #[cfg(feature = "confirmations")]
+    struct MockResolver(u32);
+
+    #[cfg(feature = "confirmations")]
+    impl ConfirmationResolver<u8> for MockResolver {
+        fn confirmation_status(&self, pub_id: u8) -> ConfirmationStatus {
+            let confirmations = self.0.saturating_sub(pub_id as u32);
+            ConfirmationStatus {
+                confirmations,
+                reorg_safe: confirmations >= 6,
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "confirmations")]
+    fn min_confirmations() {
+        let witness = witness(2, &[1], 0xA0, 7);
+        let status = witness
+            .verify_with_min_confirmations(3, &MockResolver(10), MockSeal(1), 0xA0)
+            .unwrap();
+        assert_eq!(status, ConfirmationStatus {
+            confirmations: 8,
+            reorg_safe: true
+        });
+        assert!(matches!(
+            witness.verify_with_min_confirmations(3, &MockResolver(4), MockSeal(1), 0xA0),
+            Err(SealError::Unconfirmed(2, ConfirmationStatus {
+                confirmations: 2,
+                reorg_safe: false
+            }))
+        ));
+        assert!(matches!(
+            witness.verify_with_min_confirmations(3, &MockResolver(10), MockSeal(2), 0xA0),
+            Err(SealError::NotIncluded(MockSeal(2), 2))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn error_serde() {
+        let err = witness(1, &[1, 3], 0xA0, 7)
+            .verify_seal_closing(MockSeal(2), 0xA0)
+            .unwrap_err();
+        assert_eq!(
+            serde_json::to_string(&err).unwrap(),
+            r#"{"kind":"notIncluded","seal":2,"pub_id":1,"detail":null}"#
+        );
+
+        let err = SealError::<MockSeal>::Published(MockError::Commitment);
+        assert_eq!(
+            serde_json::to_string(&err).unwrap(),
+            r#"{"kind":"published","seal":null,"pub_id":null,"detail":"commitment"}"#
+        );
+
+        let err = SealError::<MockSeal>::Client(MockError::Convolution);
+        assert_eq!(
+            serde_json::to_string(&err).unwrap(),
+            r#"{"kind":"client","seal":null,"pub_id":null,"detail":"convolution"}"#
+        );
+
+        let err = SealError::<MockSeal>::NoCanonicalWitness;
+        assert_eq!(
+            serde_json::to_string(&err).unwrap(),
+            r#"{"kind":"noCanonicalWitness","seal":null,"pub_id":null,"detail":null}"#
+        );
+
+        #[cfg(feature = "confirmations")]
+        {
+            let err = SealError::<MockSeal>::Unconfirmed(2, ConfirmationStatus {
+                confirmations: 1,
+                reorg_safe: false,
+            });
+            assert_eq!(
+                serde_json::to_string(&err).unwrap(),
+                r#"{"kind":"unconfirmed","seal":null,"pub_id":2,"detail":{"confirmations":1,"reorgSafe":false}}"#
+            );
         }
     }
 }
@@ -19,12 +19,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{HashSet, VecDeque};
 use std::fmt::{self, Debug, Display, Formatter};
 use std::hash::Hash;
 use std::ops::AddAssign;
 
 /// Result of client-side validation operation
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "camelCase"))]
 #[repr(u8)]
 pub enum Validity {
     /// The data are valid both in terms of the internal consistency and in
@@ -57,7 +59,15 @@ impl Display for Validity {
 /// Marker trait for all types of validation log entries (failures, trust
 /// issues, warnings, info messages) contained within a [`ValidationReport`]
 /// produced during client-side-validation.
-pub trait ValidationLog: Clone + Eq + Hash + Debug + Display {}
+pub trait ValidationLog: Clone + Eq + Hash + Debug + Display {
+    /// Returns code identifying the kind of the log entry, which is used as a
+    /// rule id in machine-readable reports (see `Status::to_sarif`).
+    ///
+    /// The code must be stable across releases, such that the reports can be
+    /// compared with each other; enums should return distinct codes for their
+    /// variants.
+    fn validation_code(&self) -> String;
+}
 
 #[cfg(feature = "serde")]
 /// Marker trait for all types of validation log entries (failures, trust
@@ -66,6 +76,13 @@ pub trait ValidationLog: Clone + Eq + Hash + Debug + Display {}
 pub trait ValidationLog:
     Clone + Eq + Hash + Debug + Display + serde::Serialize + for<'de> serde::Deserialize<'de>
 {
+    /// Returns code identifying the kind of the log entry, which is used as a
+    /// rule id in machine-readable reports (see `Status::to_sarif`).
+    ///
+    /// The code must be stable across releases, such that the reports can be
+    /// compared with each other; enums should return distinct codes for their
+    /// variants.
+    fn validation_code(&self) -> String;
 }
 
 /// Trait for concrete implementations of seal resolution issues reported by
@@ -258,6 +275,290 @@ where R: ValidationReport
             Validity::Valid
         }
     }
+
+    /// Computes difference between the `previous` status and this one.
+    ///
+    /// Failures and seal issues are matched by their equality, thus their
+    /// implementations must include all data identifying the failure (like the
+    /// seal and the kind of the failure) and must not include any data which
+    /// may vary between validation runs.
+    pub fn diff(&self, previous: &Self) -> StatusDiff<R> {
+        fn missing<T: Clone + Eq + Hash>(items: &[T], other: &[T]) -> Vec<T> {
+            let other = other.iter().collect::<HashSet<_>>();
+            items
+                .iter()
+                .filter(|item| !other.contains(item))
+                .cloned()
+                .collect()
+        }
+
+        let (before, after) = (previous.validity(), self.validity());
+        StatusDiff {
+            new_failures: missing(&self.failures, &previous.failures),
+            resolved_failures: missing(&previous.failures, &self.failures),
+            new_seal_issues: missing(&self.seal_issues, &previous.seal_issues),
+            resolved_seal_issues: missing(&previous.seal_issues, &self.seal_issues),
+            validity: (before != after).then_some((before, after)),
+        }
+    }
+}
+
+/// Difference between two client-side-validation statuses, produced by
+/// [`Status::diff`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct StatusDiff<R>
+where R: ValidationReport
+{
+    /// Failures which were not present in the previous status.
+    pub new_failures: Vec<R::Failure>,
+
+    /// Failures from the previous status which are not present anymore.
+    pub resolved_failures: Vec<R::Failure>,
+
+    /// Seal issues which were not present in the previous status.
+    pub new_seal_issues: Vec<R::SealIssue>,
+
+    /// Seal issues from the previous status which are not present anymore.
+    pub resolved_seal_issues: Vec<R::SealIssue>,
+
+    /// Previous and current validity, if the validity has changed.
+    pub validity: Option<(Validity, Validity)>,
+}
+
+impl<R> StatusDiff<R>
+where R: ValidationReport
+{
+    /// Detects whether the statuses have the same failures, seal issues and
+    /// validity.
+    pub fn is_empty(&self) -> bool {
+        self.new_failures.is_empty() &&
+            self.resolved_failures.is_empty() &&
+            self.new_seal_issues.is_empty() &&
+            self.resolved_seal_issues.is_empty() &&
+            self.validity.is_none()
+    }
+}
+
+#[cfg(feature = "sarif")]
+impl<R> Status<R>
+where R: ValidationReport
+{
+    /// Exports validation failures and seal issues from the status into
+    /// [SARIF] 2.1.0 format, such that they can be presented by CI systems.
+    ///
+    /// Each failure is reported as a SARIF result with `error` level, and each
+    /// seal issue as a result with `warning` level. The rule ids are provided
+    /// by [`ValidationLog::validation_code`]. Warnings and information
+    /// messages are not exported.
+    ///
+    /// [SARIF]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+    pub fn to_sarif(&self) -> serde_json::Value {
+        use std::collections::BTreeSet;
+
+        use serde_json::json;
+
+        fn result(log: &impl ValidationLog, level: &str) -> serde_json::Value {
+            json!({
+                "ruleId": log.validation_code(),
+                "level": level,
+                "message": { "text": log.to_string() },
+            })
+        }
+
+        let results = self
+            .failures
+            .iter()
+            .map(|failure| result(failure, "error"))
+            .chain(
+                self.seal_issues
+                    .iter()
+                    .map(|issue| result(issue, "warning")),
+            )
+            .collect::<Vec<_>>();
+        let rules = self
+            .failures
+            .iter()
+            .map(ValidationLog::validation_code)
+            .chain(self.seal_issues.iter().map(ValidationLog::validation_code))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|id| json!({ "id": id }))
+            .collect::<Vec<_>>();
+
+        json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    }
+                },
+                "results": results,
+            }],
+        })
+    }
+}
+
+/// Checkpoint of an interrupted client-side-validation process, capturing the
+/// validated prefix of the data. Can be persisted and later used to resume the
+/// validation with [`ClientSideValidate::client_side_validate_resume`].
+///
+/// [`ClientData`] items do not have identifiers, thus the validated prefix is
+/// recorded as a number of the performed validation steps. A checkpoint is
+/// valid only for the same data it was created for: if the data change in
+/// between, the validation resumes from a wrong item.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct ValidationCheckpoint<R>
+where R: ValidationReport
+{
+    /// Number of validation steps which were already performed (see
+    /// [`ClientSideValidate::client_side_validate_checkpoint`] for the
+    /// definition of a validation step).
+    pub processed: usize,
+
+    /// Validation status accumulated during the already performed steps.
+    pub status: Status<R>,
+}
+
+impl<R> Default for ValidationCheckpoint<R>
+where R: ValidationReport
+{
+    fn default() -> Self { Self::new() }
+}
+
+impl<R> ValidationCheckpoint<R>
+where R: ValidationReport
+{
+    /// Constructs checkpoint for a validation which has not yet started.
+    pub fn new() -> Self {
+        ValidationCheckpoint {
+            processed: 0,
+            status: Status::new(),
+        }
+    }
+}
+
+/// Single log event emitted during streaming client-side-validation (see
+/// [`ClientSideValidate::client_side_validate_streaming`]).
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ValidationEvent<R>
+where R: ValidationReport
+{
+    /// Seal resolution issue (see [`Status::seal_issues`]).
+    SealIssue(R::SealIssue),
+    /// Validation failure (see [`Status::failures`]).
+    Failure(R::Failure),
+    /// Validation warning (see [`Status::warnings`]).
+    Warning(R::Warning),
+    /// Validation information report (see [`Status::info`]).
+    Info(R::Info),
+    /// Final validity of the data, which is always the last event in the
+    /// stream.
+    Finished(Validity),
+}
+
+impl<R> Extend<ValidationEvent<R>> for Status<R>
+where R: ValidationReport
+{
+    fn extend<T: IntoIterator<Item = ValidationEvent<R>>>(&mut self, iter: T) {
+        for event in iter {
+            match event {
+                ValidationEvent::SealIssue(issue) => self.seal_issues.push(issue),
+                ValidationEvent::Failure(failure) => self.failures.push(failure),
+                ValidationEvent::Warning(warning) => self.warnings.push(warning),
+                ValidationEvent::Info(info) => self.info.push(info),
+                ValidationEvent::Finished(_) => {}
+            }
+        }
+    }
+}
+
+/// Iterator over log events of client-side-validation, performing the
+/// validation lazily (see
+/// [`ClientSideValidate::client_side_validate_streaming`]).
+pub struct ValidationStream<'client_data, Data, Resolver>
+where Data: ClientSideValidate<'client_data> + ?Sized
+{
+    data: &'client_data Data,
+    resolver: &'client_data mut Resolver,
+    items: Option<Data::ValidationIter>,
+    pending: VecDeque<ValidationEvent<Data::ValidationReport>>,
+    failed: bool,
+    seal_issues: bool,
+    finished: bool,
+}
+
+impl<'client_data, Data, Resolver> ValidationStream<'client_data, Data, Resolver>
+where Data: ClientSideValidate<'client_data> + ?Sized
+{
+    fn push_status(&mut self, status: Status<Data::ValidationReport>) {
+        self.seal_issues |= !status.seal_issues.is_empty();
+        self.failed |= !status.failures.is_empty();
+        self.pending.extend(
+            status
+                .seal_issues
+                .into_iter()
+                .map(ValidationEvent::SealIssue),
+        );
+        self.pending
+            .extend(status.failures.into_iter().map(ValidationEvent::Failure));
+        self.pending
+            .extend(status.warnings.into_iter().map(ValidationEvent::Warning));
+        self.pending
+            .extend(status.info.into_iter().map(ValidationEvent::Info));
+    }
+}
+
+impl<'client_data, Data, Resolver> Iterator for ValidationStream<'client_data, Data, Resolver>
+where
+    Data: ClientSideValidate<'client_data> + ?Sized,
+    Resolver: SealResolver<
+        <<<Data as ClientData<'client_data>>::ValidationReport as ValidationReport>::SealIssue as SealIssue>::Seal,
+        Error = <<Data as ClientData<'client_data>>::ValidationReport as ValidationReport>::SealIssue,
+    >,
+{
+    type Item = ValidationEvent<Data::ValidationReport>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() && !self.finished {
+            let items = match &mut self.items {
+                None => {
+                    let status = self.data.validate_internal_consistency();
+                    self.items = Some(self.data.validation_iter());
+                    self.push_status(status);
+                    continue;
+                }
+                Some(items) => items,
+            };
+            let Some(item) = items.next() else {
+                let validity = if self.failed {
+                    Validity::Invalid
+                } else if self.seal_issues {
+                    Validity::SealIssues
+                } else {
+                    Validity::Valid
+                };
+                self.pending.push_back(ValidationEvent::Finished(validity));
+                self.finished = true;
+                break;
+            };
+            let mut status = Status::new();
+            for seal in item.single_use_seals() {
+                let _ = self
+                    .resolver
+                    .resolve_trust(seal)
+                    .map_err(|issue| status.add_seal_issue(issue));
+            }
+            status += item.validate_internal_consistency();
+            self.push_status(status);
+        }
+        self.pending.pop_front()
+    }
 }
 
 /// This simple trait MUST be used by all top-level data structures implementing
@@ -310,19 +611,109 @@ where Self::ValidationItem: 'client_data
             Error = <<Self as ClientData<'client_data>>::ValidationReport as ValidationReport>::SealIssue,
         >,
     {
-        let mut status = Status::new();
+        self.client_side_validate_resume(resolver, ValidationCheckpoint::new())
+    }
 
-        status += self.validate_internal_consistency();
-        for item in self.validation_iter() {
+    /// Resumes client-side-validation from a given `checkpoint`, proceeding up
+    /// to the end of the client-side-validated data and returning the complete
+    /// [`Status`] log, which includes all reports accumulated before the
+    /// checkpoint was created.
+    ///
+    /// The checkpoint must be created for the same data with
+    /// [`ClientSideValidate::client_side_validate_checkpoint`] method;
+    /// otherwise the produced status is meaningless.
+    fn client_side_validate_resume<Resolver>(
+        &'client_data self,
+        resolver: &'client_data mut Resolver,
+        checkpoint: ValidationCheckpoint<Self::ValidationReport>,
+    ) -> Status<Self::ValidationReport>
+    where
+        Resolver: SealResolver<
+            <<<Self as ClientData<'client_data>>::ValidationReport as ValidationReport>::SealIssue as SealIssue>::Seal,
+            Error = <<Self as ClientData<'client_data>>::ValidationReport as ValidationReport>::SealIssue,
+        >,
+    {
+        self.client_side_validate_checkpoint(resolver, checkpoint, usize::MAX)
+            .status
+    }
+
+    /// Performs at most `steps` of client-side-validation starting from the
+    /// provided `checkpoint` (use [`ValidationCheckpoint::new`] to start from
+    /// the beginning) and returns a new checkpoint, which can be persisted and
+    /// later used to continue the validation with
+    /// [`ClientSideValidate::client_side_validate_resume`].
+    ///
+    /// The first validation step is the check of the internal consistency of
+    /// the top-level data; each of the following steps validates a single
+    /// item returned by [`ClientSideValidate::validation_iter`], which must
+    /// iterate the items in a deterministic order.
+    fn client_side_validate_checkpoint<Resolver>(
+        &'client_data self,
+        resolver: &'client_data mut Resolver,
+        checkpoint: ValidationCheckpoint<Self::ValidationReport>,
+        steps: usize,
+    ) -> ValidationCheckpoint<Self::ValidationReport>
+    where
+        Resolver: SealResolver<
+            <<<Self as ClientData<'client_data>>::ValidationReport as ValidationReport>::SealIssue as SealIssue>::Seal,
+            Error = <<Self as ClientData<'client_data>>::ValidationReport as ValidationReport>::SealIssue,
+        >,
+    {
+        let ValidationCheckpoint {
+            mut processed,
+            mut status,
+        } = checkpoint;
+        let mut remaining = steps;
+
+        if processed == 0 && remaining > 0 {
+            status += self.validate_internal_consistency();
+            processed += 1;
+            remaining -= 1;
+        }
+        for item in self
+            .validation_iter()
+            .skip(processed.saturating_sub(1))
+            .take(remaining)
+        {
             for seal in item.single_use_seals() {
                 let _ = resolver
                     .resolve_trust(seal)
                     .map_err(|issue| status.add_seal_issue(issue));
             }
             status += item.validate_internal_consistency();
+            processed += 1;
         }
 
-        status
+        ValidationCheckpoint { processed, status }
+    }
+
+    /// Performs client-side-validation lazily, returning iterator over the
+    /// log events in the order they are produced, such that the results of a
+    /// large validation can be processed incrementally.
+    ///
+    /// The last event of the stream is always [`ValidationEvent::Finished`]
+    /// with the final validity of the data. Collecting the stream into
+    /// [`Status`] with [`Extend`] produces the same status as
+    /// [`ClientSideValidate::client_side_validate`].
+    fn client_side_validate_streaming<Resolver>(
+        &'client_data self,
+        resolver: &'client_data mut Resolver,
+    ) -> ValidationStream<'client_data, Self, Resolver>
+    where
+        Resolver: SealResolver<
+            <<<Self as ClientData<'client_data>>::ValidationReport as ValidationReport>::SealIssue as SealIssue>::Seal,
+            Error = <<Self as ClientData<'client_data>>::ValidationReport as ValidationReport>::SealIssue,
+        >,
+    {
+        ValidationStream {
+            data: self,
+            resolver,
+            items: None,
+            pending: VecDeque::new(),
+            failed: false,
+            seal_issues: false,
+            finished: false,
+        }
     }
 
     /// Returns iterator over hierarchy of individual data items inside
@@ -389,3 +780,252 @@ pub trait SealResolver<Seal> {
     /// (like network connectivity) or if the seal is not (yet) closed.
     fn resolve_trust(&mut self, seal: &Seal) -> Result<(), Self::Error>;
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub struct Unclosed(pub u8);
+
+    impl Display for Unclosed {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            write!(f, "seal {} is not closed", self.0)
+        }
+    }
+    impl std::error::Error for Unclosed {}
+
+    impl ValidationLog for Unclosed {
+        fn validation_code(&self) -> String { "unclosedSeal".to_owned() }
+    }
+    impl SealIssue for Unclosed {
+        type Seal = u8;
+        fn seal(&self) -> &Self::Seal { &self.0 }
+    }
+
+    #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    pub struct Invalid(pub u8);
+
+    impl Display for Invalid {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            write!(f, "item {} is invalid", self.0)
+        }
+    }
+    impl std::error::Error for Invalid {}
+
+    impl ValidationLog for Invalid {
+        fn validation_code(&self) -> String { "invalidItem".to_owned() }
+    }
+    impl ValidationFailure for Invalid {}
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+    pub struct Report;
+    impl ValidationReport for Report {
+        type SealIssue = Unclosed;
+        type Failure = Invalid;
+        type Warning = String;
+        type Info = String;
+    }
+
+    pub struct Item {
+        pub id: u8,
+        pub seals: Vec<u8>,
+        pub valid: bool,
+    }
+
+    impl<'a> ClientData<'a> for Item {
+        type ValidationReport = Report;
+        type SealIterator = std::slice::Iter<'a, u8>;
+
+        fn single_use_seals(&'a self) -> Self::SealIterator { self.seals.iter() }
+
+        fn validate_internal_consistency(&'a self) -> Status<Report> {
+            let mut status = Status::new();
+            if !self.valid {
+                status.add_failure(Invalid(self.id));
+            }
+            status.add_info(format!("item {} validated", self.id));
+            status
+        }
+    }
+
+    pub struct History(pub Vec<Item>);
+
+    impl<'a> ClientData<'a> for History {
+        type ValidationReport = Report;
+        type SealIterator = std::slice::Iter<'a, u8>;
+
+        fn single_use_seals(&'a self) -> Self::SealIterator { [].iter() }
+
+        fn validate_internal_consistency(&'a self) -> Status<Report> {
+            let mut status = Status::new();
+            if self.0.is_empty() {
+                status.add_warning("empty history".to_owned());
+            }
+            status
+        }
+    }
+
+    impl<'a> ClientSideValidate<'a> for History {
+        type ValidationItem = Item;
+        type ValidationIter = std::slice::Iter<'a, Item>;
+
+        fn validation_iter(&'a self) -> Self::ValidationIter { self.0.iter() }
+    }
+
+    #[derive(Clone, Default)]
+    pub struct Resolver(pub BTreeSet<u8>);
+
+    impl SealResolver<u8> for Resolver {
+        type Error = Unclosed;
+
+        fn resolve_trust(&mut self, seal: &u8) -> Result<(), Self::Error> {
+            if self.0.contains(seal) {
+                return Err(Unclosed(*seal));
+            }
+            Ok(())
+        }
+    }
+
+    pub fn history() -> History {
+        History(
+            (0..10u8)
+                .map(|id| Item {
+                    id,
+                    seals: vec![id * 2, id * 2 + 1],
+                    valid: id % 3 != 0,
+                })
+                .collect(),
+        )
+    }
+
+    pub fn resolver() -> Resolver { Resolver(BTreeSet::from([3, 8, 15])) }
+
+    #[test]
+    fn checkpoint_resume() {
+        let history = history();
+
+        let mut resolver1 = resolver();
+        let full = history.client_side_validate(&mut resolver1);
+        assert_eq!(full.validity(), Validity::Invalid);
+        assert_eq!(full.failures.len(), 4);
+        assert_eq!(full.seal_issues.len(), 3);
+
+        let mut resolver2 = resolver();
+        let checkpoint =
+            history.client_side_validate_checkpoint(&mut resolver2, ValidationCheckpoint::new(), 6);
+        assert_eq!(checkpoint.processed, 6);
+        assert_ne!(checkpoint.status, full);
+        #[cfg(feature = "serde")]
+        let checkpoint = {
+            let json = serde_json::to_string(&checkpoint).unwrap();
+            let restored = serde_json::from_str::<ValidationCheckpoint<Report>>(&json).unwrap();
+            assert_eq!(restored, checkpoint);
+            restored
+        };
+        let mut resolver3 = resolver();
+        let resumed = history.client_side_validate_resume(&mut resolver3, checkpoint);
+        assert_eq!(resumed, full);
+    }
+
+    #[test]
+    fn streaming() {
+        let history = history();
+        let mut resolver1 = resolver();
+        let full = history.client_side_validate(&mut resolver1);
+
+        let mut resolver2 = resolver();
+        let events = history
+            .client_side_validate_streaming(&mut resolver2)
+            .collect::<Vec<_>>();
+        assert_eq!(events.last(), Some(&ValidationEvent::Finished(full.validity())));
+        assert_eq!(events.len(), 4 + 3 + 10 + 1);
+        let mut status = Status::new();
+        status.extend(events);
+        assert_eq!(status, full);
+
+        let empty = History(vec![]);
+        let mut resolver3 = resolver();
+        let events = empty
+            .client_side_validate_streaming(&mut resolver3)
+            .collect::<Vec<_>>();
+        assert_eq!(events, vec![
+            ValidationEvent::Warning("empty history".to_owned()),
+            ValidationEvent::Finished(Validity::Valid)
+        ]);
+    }
+
+    #[test]
+    fn checkpoint_empty() {
+        let history = history();
+        let mut resolver1 = resolver();
+        let full = history.client_side_validate(&mut resolver1);
+
+        let mut resolver2 = resolver();
+        let checkpoint =
+            history.client_side_validate_checkpoint(&mut resolver2, ValidationCheckpoint::new(), 0);
+        assert_eq!(checkpoint, ValidationCheckpoint::new());
+        let mut resolver3 = resolver();
+        let resumed = history.client_side_validate_resume(&mut resolver3, checkpoint);
+        assert_eq!(resumed, full);
+    }
+
+    #[test]
+    fn status_diff() {
+        let history = history();
+        let mut resolver1 = resolver();
+        let previous = history.client_side_validate(&mut resolver1);
+        assert!(previous.diff(&previous).is_empty());
+
+        let mut resolver2 = Resolver(BTreeSet::from([3, 8]));
+        let current = history.client_side_validate(&mut resolver2);
+        let diff = current.diff(&previous);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.resolved_seal_issues, vec![Unclosed(15)]);
+        assert!(diff.new_seal_issues.is_empty());
+        assert!(diff.new_failures.is_empty());
+        assert!(diff.resolved_failures.is_empty());
+        assert_eq!(diff.validity, None);
+
+        let previous = Status::<Report>::from_failure(Invalid(3));
+        let mut current = Status::<Report>::new();
+        current.add_seal_issue(Unclosed(1));
+        let diff = current.diff(&previous);
+        assert_eq!(diff.resolved_failures, vec![Invalid(3)]);
+        assert_eq!(diff.new_seal_issues, vec![Unclosed(1)]);
+        assert_eq!(diff.validity, Some((Validity::Invalid, Validity::SealIssues)));
+    }
+
+    #[test]
+    #[cfg(feature = "sarif")]
+    fn sarif() {
+        let status = Status::<Report>::from_failure(Invalid(3));
+        let sarif = status.to_sarif();
+        assert_eq!(sarif["version"], "2.1.0");
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ruleId"], "invalidItem");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[0]["message"]["text"], "item 3 is invalid");
+        assert_eq!(sarif["runs"][0]["tool"]["driver"]["rules"][0]["id"], "invalidItem");
+
+        let mut resolver = resolver();
+        let status = history().client_side_validate(&mut resolver);
+        let sarif = status.to_sarif();
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 7);
+        assert_eq!(results[6]["ruleId"], "unclosedSeal");
+        assert_eq!(results[6]["level"], "warning");
+        assert_eq!(
+            sarif["runs"][0]["tool"]["driver"]["rules"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+}
@@ -58,6 +58,7 @@ extern crate serde;
 mod api;
 
 pub use api::{
-    ClientData, ClientSideValidate, SealIssue, SealResolver, Status, ValidationFailure,
-    ValidationLog, ValidationReport, Validity,
+    ClientData, ClientSideValidate, SealIssue, SealResolver, Status, StatusDiff,
+    ValidationCheckpoint, ValidationEvent, ValidationFailure, ValidationLog, ValidationReport,
+    ValidationStream, Validity,
 };